@@ -0,0 +1,2 @@
+-- Mazes stored before topologies existed were always solved on a torus.
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS topology TEXT NOT NULL DEFAULT 'torus';
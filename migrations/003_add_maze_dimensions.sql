@@ -0,0 +1,11 @@
+-- Size columns let the list endpoint filter and sort without reading content.
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS rows INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS cols INTEGER NOT NULL DEFAULT 0;
+
+UPDATE mazes
+SET rows = COALESCE(array_length(string_to_array(rtrim(content, E'\n'), E'\n'), 1), 0),
+    cols = char_length(split_part(content, E'\n', 1));
+
+CREATE INDEX IF NOT EXISTS mazes_created_at_idx ON mazes (created_at, id);
+CREATE INDEX IF NOT EXISTS mazes_name_idx ON mazes (name, id);
+CREATE INDEX IF NOT EXISTS mazes_size_idx ON mazes ((rows * cols), id);
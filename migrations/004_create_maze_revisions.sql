@@ -0,0 +1,16 @@
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS revision INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP WITH TIME ZONE;
+UPDATE mazes SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE mazes ALTER COLUMN updated_at SET DEFAULT NOW();
+ALTER TABLE mazes ALTER COLUMN updated_at SET NOT NULL;
+
+-- Every version of a maze that an update replaced.
+CREATE TABLE IF NOT EXISTS maze_revisions (
+    maze_id UUID NOT NULL REFERENCES mazes (id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    topology TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (maze_id, revision)
+);
@@ -0,0 +1,11 @@
+-- Distance fields from a single cell, reused until the maze changes.
+CREATE TABLE IF NOT EXISTS maze_distances (
+    maze_id UUID NOT NULL REFERENCES mazes (id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    from_row INTEGER NOT NULL,
+    from_col INTEGER NOT NULL,
+    -- The distance grid as JSON, one array per row with null for unreachable cells.
+    distances TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (maze_id, revision, from_row, from_col)
+);
@@ -0,0 +1,11 @@
+-- Analysis figures for filtering and sorting the list. Both stay NULL for
+-- content that does not parse; difficulty is also NULL for unsolvable
+-- mazes. The server fills in rows from before this migration at startup
+-- and marks them analyzed, whether or not their content parses.
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS difficulty INTEGER;
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS dead_ends INTEGER;
+ALTER TABLE mazes ADD COLUMN IF NOT EXISTS analyzed BOOLEAN NOT NULL DEFAULT TRUE;
+UPDATE mazes SET analyzed = FALSE;
+
+CREATE INDEX IF NOT EXISTS mazes_difficulty_idx ON mazes ((COALESCE(difficulty, -1)), id);
+CREATE INDEX IF NOT EXISTS mazes_unanalyzed_idx ON mazes (id) WHERE NOT analyzed;
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS mazes (
+    id BLOB PRIMARY KEY,
+    name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
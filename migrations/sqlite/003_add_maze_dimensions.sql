@@ -0,0 +1,14 @@
+-- Size columns let the list endpoint filter and sort without reading content.
+ALTER TABLE mazes ADD COLUMN rows INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE mazes ADD COLUMN cols INTEGER NOT NULL DEFAULT 0;
+
+UPDATE mazes
+SET rows = length(rtrim(content, char(10))) - length(replace(rtrim(content, char(10)), char(10), '')) + 1,
+    cols = CASE
+        WHEN instr(content, char(10)) > 0 THEN instr(content, char(10)) - 1
+        ELSE length(content)
+    END;
+
+CREATE INDEX IF NOT EXISTS mazes_created_at_idx ON mazes (created_at, id);
+CREATE INDEX IF NOT EXISTS mazes_name_idx ON mazes (name, id);
+CREATE INDEX IF NOT EXISTS mazes_size_idx ON mazes ((rows * cols), id);
@@ -0,0 +1,14 @@
+ALTER TABLE mazes ADD COLUMN revision INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE mazes ADD COLUMN updated_at TEXT;
+UPDATE mazes SET updated_at = created_at WHERE updated_at IS NULL;
+
+-- Every version of a maze that an update replaced.
+CREATE TABLE IF NOT EXISTS maze_revisions (
+    maze_id BLOB NOT NULL REFERENCES mazes (id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    topology TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (maze_id, revision)
+);
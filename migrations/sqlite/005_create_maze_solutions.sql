@@ -0,0 +1,15 @@
+-- Solver results, reused until the maze changes.
+CREATE TABLE IF NOT EXISTS maze_solutions (
+    maze_id BLOB NOT NULL REFERENCES mazes (id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    start_row INTEGER NOT NULL,
+    start_col INTEGER NOT NULL,
+    end_row INTEGER NOT NULL,
+    end_col INTEGER NOT NULL,
+    algorithm TEXT NOT NULL,
+    options TEXT NOT NULL,
+    -- The solution response as JSON, NULL when there is no path.
+    solution TEXT,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (maze_id, revision, start_row, start_col, end_row, end_col, algorithm, options)
+);
@@ -0,0 +1,468 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+use uuid::Uuid;
+
+use super::listing::MazeFilter;
+use super::store::{MazeStore, StoreError};
+use crate::domain::Topology;
+use crate::entities::{MazeResponse, MazeRevision, MazeSummary};
+
+// Serves a directory of maze files such as `maps/`. Each maze is a
+// `<stem>.txt` file with a `<stem>.json` sidecar holding everything that is
+// not the maze itself. Text files without a sidecar are still served: their
+// id is derived from the file name, so it stays the same across restarts.
+// Replaced versions are kept as `.revisions/<stem>/<revision>.json`.
+#[derive(Clone)]
+pub struct FileMazeRepository {
+    dir: PathBuf,
+    mazes: Arc<RwLock<HashMap<Uuid, Entry>>>,
+    // What the last scan could not load.
+    errors: Arc<RwLock<Vec<ScanError>>>,
+    watcher: Option<Arc<RecommendedWatcher>>,
+}
+
+// A file left out of the last scan, or the directory itself when a reload
+// failed and the mazes from before it are still being served.
+#[derive(Debug, Clone)]
+pub struct ScanError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+struct Scan {
+    mazes: HashMap<Uuid, Entry>,
+    errors: Vec<ScanError>,
+}
+
+#[derive(Clone)]
+struct Entry {
+    stem: String,
+    maze: MazeResponse,
+    // Kept up to date with `maze` so listing does not analyze every maze.
+    summary: MazeSummary,
+    // When the maze file was last written.
+    modified: SystemTime,
+}
+
+impl Entry {
+    fn new(stem: String, maze: MazeResponse, modified: SystemTime) -> Self {
+        Self {
+            stem,
+            summary: MazeSummary::from(&maze),
+            maze,
+            modified,
+        }
+    }
+
+    // The watcher rescans every file on each change, so the analysis is
+    // carried over from the last scan while the maze file and topology stay
+    // the same. Only the sidecar may have changed.
+    fn rescanned(
+        stem: String,
+        maze: MazeResponse,
+        modified: SystemTime,
+        previous: Option<&Entry>,
+    ) -> Self {
+        let unchanged = previous.filter(|previous| {
+            previous.stem == stem
+                && previous.modified == modified
+                && previous.maze.topology == maze.topology
+        });
+        let Some(previous) = unchanged else {
+            return Self::new(stem, maze, modified);
+        };
+        Self {
+            stem,
+            summary: MazeSummary {
+                id: maze.id,
+                name: maze.name.clone(),
+                created_at: maze.created_at,
+                ..previous.summary.clone()
+            },
+            maze,
+            modified,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Metadata {
+    id: Uuid,
+    name: String,
+    #[serde(default = "unwrapped")]
+    topology: Topology,
+    #[serde(default = "first_revision")]
+    revision: u32,
+    created_at: DateTime<Utc>,
+    // Falls back to `created_at` for mazes that were never updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<DateTime<Utc>>,
+}
+
+fn first_revision() -> u32 {
+    1
+}
+
+// Hand-written mazes have solid edges unless their sidecar says otherwise.
+fn unwrapped() -> Topology {
+    Topology::Bounded
+}
+
+impl Metadata {
+    fn into_maze(self, content: String) -> MazeResponse {
+        MazeResponse {
+            id: self.id,
+            name: self.name,
+            content,
+            topology: self.topology,
+            revision: self.revision,
+            created_at: self.created_at,
+            updated_at: self.updated_at.unwrap_or(self.created_at),
+        }
+    }
+}
+
+impl FileMazeRepository {
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, StoreError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        let scan = scan(&dir, &HashMap::new())?;
+        Ok(Self {
+            dir,
+            mazes: Arc::new(RwLock::new(scan.mazes)),
+            errors: Arc::new(RwLock::new(scan.errors)),
+            watcher: None,
+        })
+    }
+
+    pub fn scan_errors(&self) -> Vec<ScanError> {
+        self.errors.read().unwrap().clone()
+    }
+
+    // Re-reads the directory whenever something in it changes, so files
+    // edited by hand show up without a restart.
+    pub fn watch(mut self) -> Result<Self, StoreError> {
+        let store = self.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let reloaded = event
+                    .map_err(io::Error::other)
+                    .and_then(|_| store.reload().map_err(io::Error::other));
+                if let Err(e) = reloaded {
+                    *store.errors.write().unwrap() = vec![ScanError {
+                        path: store.dir.clone(),
+                        message: e.to_string(),
+                    }];
+                }
+            })
+            .map_err(io::Error::other)?;
+        watcher
+            .watch(&self.dir, RecursiveMode::NonRecursive)
+            .map_err(io::Error::other)?;
+
+        self.watcher = Some(Arc::new(watcher));
+        Ok(self)
+    }
+
+    pub fn reload(&self) -> Result<(), StoreError> {
+        let scan = scan(&self.dir, &self.mazes.read().unwrap())?;
+        *self.mazes.write().unwrap() = scan.mazes;
+        *self.errors.write().unwrap() = scan.errors;
+        Ok(())
+    }
+
+    // Runs file system work off the async runtime.
+    async fn blocking<T, F>(&self, work: F) -> Result<T, StoreError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> Result<T, StoreError> + Send + 'static,
+    {
+        let store = self.clone();
+        tokio::task::spawn_blocking(move || work(&store))
+            .await
+            .map_err(io::Error::other)?
+    }
+
+    fn path(&self, stem: &str, extension: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", stem, extension))
+    }
+
+    fn revisions_dir(&self, stem: &str) -> PathBuf {
+        self.dir.join(".revisions").join(stem)
+    }
+
+    // Returns when the maze file was written.
+    fn save(&self, stem: &str, maze: &MazeResponse) -> io::Result<SystemTime> {
+        let metadata = Metadata {
+            id: maze.id,
+            name: maze.name.clone(),
+            topology: maze.topology,
+            revision: maze.revision,
+            created_at: maze.created_at,
+            updated_at: Some(maze.updated_at),
+        };
+
+        // The sidecar goes first: a maze file is picked up as soon as it
+        // appears and must not be given a made-up id in the meantime.
+        let json = serde_json::to_string_pretty(&metadata).map_err(io::Error::other)?;
+        write_atomic(&self.path(stem, "json"), &json)?;
+        let path = self.path(stem, "txt");
+        write_atomic(&path, &format!("{}\n", maze.content))?;
+        fs::metadata(path)?.modified()
+    }
+
+    fn read_revision(&self, stem: &str, revision: u32) -> io::Result<Option<MazeRevision>> {
+        let path = self.revisions_dir(stem).join(format!("{}.json", revision));
+        match fs::read_to_string(path) {
+            Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn stem(&self, id: Uuid) -> Option<String> {
+        self.mazes
+            .read()
+            .unwrap()
+            .get(&id)
+            .map(|entry| entry.stem.clone())
+    }
+}
+
+// Only fails when the directory cannot be listed. A file that cannot be
+// read is left out and reported, so one bad file does not hide the rest.
+// `previous` is what the last scan found.
+fn scan(dir: &Path, previous: &HashMap<Uuid, Entry>) -> io::Result<Scan> {
+    let mut scan = Scan {
+        mazes: HashMap::new(),
+        errors: Vec::new(),
+    };
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("txt") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        match load(&path, stem) {
+            Ok((maze, modified)) => {
+                let (id, previous) = (maze.id, previous.get(&maze.id));
+                let entry = Entry::rescanned(stem.to_string(), maze, modified, previous);
+                scan.mazes.insert(id, entry);
+            }
+            Err(e) => scan.errors.push(ScanError {
+                path,
+                message: e.to_string(),
+            }),
+        }
+    }
+    Ok(scan)
+}
+
+// The maze with when its file was last written.
+fn load(path: &Path, stem: &str) -> io::Result<(MazeResponse, SystemTime)> {
+    let modified = fs::metadata(path)?.modified()?;
+    let content = fs::read_to_string(path)?;
+    let sidecar = path.with_extension("json");
+    let metadata = if sidecar.exists() {
+        serde_json::from_str(&fs::read_to_string(&sidecar)?)?
+    } else {
+        Metadata {
+            id: Uuid::new_v5(&Uuid::NAMESPACE_URL, stem.as_bytes()),
+            name: stem.to_string(),
+            topology: unwrapped(),
+            revision: first_revision(),
+            created_at: modified.into(),
+            updated_at: None,
+        }
+    };
+    let content = content.strip_suffix('\n').unwrap_or(&content).to_string();
+    Ok((metadata.into_maze(content), modified))
+}
+
+// Writes through a temporary file so a reload never sees half a file.
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(&tmp, path)
+}
+
+#[async_trait]
+impl MazeStore for FileMazeRepository {
+    async fn create(
+        &self,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<MazeResponse, StoreError> {
+        let now = Utc::now();
+        let maze = MazeResponse {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            content: content.to_string(),
+            topology,
+            revision: first_revision(),
+            created_at: now,
+            updated_at: now,
+        };
+        self.blocking(move |store| {
+            let stem = maze.id.to_string();
+            let modified = store.save(&stem, &maze)?;
+
+            store
+                .mazes
+                .write()
+                .unwrap()
+                .insert(maze.id, Entry::new(stem, maze.clone(), modified));
+            Ok(maze)
+        })
+        .await
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<MazeResponse>, StoreError> {
+        Ok(self
+            .mazes
+            .read()
+            .unwrap()
+            .get(&id)
+            .map(|entry| entry.maze.clone()))
+    }
+
+    async fn list(&self, filter: &MazeFilter) -> Result<Vec<MazeSummary>, StoreError> {
+        Ok(filter.apply(
+            self.mazes
+                .read()
+                .unwrap()
+                .values()
+                .map(|entry| entry.summary.clone()),
+        ))
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<Option<MazeResponse>, StoreError> {
+        let (name, content) = (name.to_string(), content.to_string());
+        self.blocking(move |store| store.replace(id, name, content, topology))
+            .await
+    }
+
+    async fn revisions(&self, id: Uuid) -> Result<Vec<MazeRevision>, StoreError> {
+        let Some(stem) = self.stem(id) else {
+            return Ok(Vec::new());
+        };
+        self.blocking(move |store| store.read_revisions(&stem))
+            .await
+    }
+
+    async fn get_revision(
+        &self,
+        id: Uuid,
+        revision: u32,
+    ) -> Result<Option<MazeRevision>, StoreError> {
+        match self.stem(id) {
+            Some(stem) => {
+                self.blocking(move |store| Ok(store.read_revision(&stem, revision)?))
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, StoreError> {
+        let Some(entry) = self.mazes.write().unwrap().remove(&id) else {
+            return Ok(false);
+        };
+        self.blocking(move |store| store.remove_files(&entry.stem))
+            .await
+    }
+}
+
+// The blocking halves of the store methods above.
+impl FileMazeRepository {
+    fn replace(
+        &self,
+        id: Uuid,
+        name: String,
+        content: String,
+        topology: Topology,
+    ) -> Result<Option<MazeResponse>, StoreError> {
+        let mut mazes = self.mazes.write().unwrap();
+        let Some(entry) = mazes.get_mut(&id) else {
+            return Ok(None);
+        };
+
+        let previous = MazeRevision {
+            revision: entry.maze.revision,
+            name: entry.maze.name.clone(),
+            content: entry.maze.content.clone(),
+            topology: entry.maze.topology,
+            created_at: entry.maze.updated_at,
+        };
+        let revisions = self.revisions_dir(&entry.stem);
+        fs::create_dir_all(&revisions)?;
+        let json = serde_json::to_string_pretty(&previous).map_err(io::Error::other)?;
+        write_atomic(
+            &revisions.join(format!("{}.json", previous.revision)),
+            &json,
+        )?;
+
+        let maze = MazeResponse {
+            name,
+            content,
+            topology,
+            revision: previous.revision + 1,
+            updated_at: Utc::now(),
+            ..entry.maze.clone()
+        };
+        entry.modified = self.save(&entry.stem, &maze)?;
+        entry.summary = MazeSummary::from(&maze);
+        entry.maze = maze.clone();
+        Ok(Some(maze))
+    }
+
+    fn read_revisions(&self, stem: &str) -> Result<Vec<MazeRevision>, StoreError> {
+        let dir = match fs::read_dir(self.revisions_dir(stem)) {
+            Ok(dir) => dir,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut revisions = Vec::new();
+        for entry in dir {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                let json = fs::read_to_string(&path)?;
+                revisions
+                    .push(serde_json::from_str::<MazeRevision>(&json).map_err(io::Error::from)?);
+            }
+        }
+        revisions.sort_by_key(|revision| std::cmp::Reverse(revision.revision));
+        Ok(revisions)
+    }
+
+    fn remove_files(&self, stem: &str) -> Result<bool, StoreError> {
+        match fs::remove_dir_all(self.revisions_dir(stem)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        fs::remove_file(self.path(stem, "txt"))?;
+        match fs::remove_file(self.path(stem, "json")) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(true),
+        }
+    }
+}
@@ -0,0 +1,414 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Database, Encode, QueryBuilder, Type};
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+use crate::domain::{grade, Map, Topology};
+use crate::entities::{MazeListQuery, MazeResponse, MazeSort, MazeSummary, SortOrder};
+
+pub const DEFAULT_LIMIT: usize = 50;
+pub const MAX_LIMIT: usize = 500;
+// Mazes analyzed per query when filling in rows from before the analysis
+// columns.
+pub const BACKFILL_BATCH: i64 = 100;
+
+// A validated `MazeListQuery`, ready for a store to run.
+#[derive(Debug, Clone)]
+pub struct MazeFilter {
+    pub name: Option<String>,
+    pub min_rows: Option<usize>,
+    pub max_rows: Option<usize>,
+    pub min_cols: Option<usize>,
+    pub max_cols: Option<usize>,
+    pub min_difficulty: Option<u32>,
+    pub max_difficulty: Option<u32>,
+    pub min_dead_ends: Option<usize>,
+    pub max_dead_ends: Option<usize>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub sort: MazeSort,
+    pub order: SortOrder,
+    pub limit: usize,
+    pub offset: usize,
+    // Only entries strictly after this one, in sort order.
+    pub after: Option<Cursor>,
+}
+
+impl Default for MazeFilter {
+    fn default() -> Self {
+        Self {
+            name: None,
+            min_rows: None,
+            max_rows: None,
+            min_cols: None,
+            max_cols: None,
+            min_difficulty: None,
+            max_difficulty: None,
+            min_dead_ends: None,
+            max_dead_ends: None,
+            created_after: None,
+            created_before: None,
+            sort: MazeSort::default(),
+            order: MazeSort::default().default_order(),
+            limit: DEFAULT_LIMIT,
+            offset: 0,
+            after: None,
+        }
+    }
+}
+
+impl TryFrom<MazeListQuery> for MazeFilter {
+    type Error = String;
+
+    fn try_from(query: MazeListQuery) -> Result<Self, Self::Error> {
+        let sort = query.sort.unwrap_or_default();
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+        if limit == 0 || limit > MAX_LIMIT {
+            return Err(format!("limit must be between 1 and {}", MAX_LIMIT));
+        }
+        if query.cursor.is_some() && query.offset.is_some() {
+            return Err("use either cursor or offset, not both".to_string());
+        }
+
+        Ok(Self {
+            name: query.name.filter(|name| !name.is_empty()),
+            min_rows: query.min_rows,
+            max_rows: query.max_rows,
+            min_cols: query.min_cols,
+            max_cols: query.max_cols,
+            min_difficulty: query.min_difficulty,
+            max_difficulty: query.max_difficulty,
+            min_dead_ends: query.min_dead_ends,
+            max_dead_ends: query.max_dead_ends,
+            created_after: query.created_after,
+            created_before: query.created_before,
+            sort,
+            order: query.order.unwrap_or(sort.default_order()),
+            limit,
+            offset: query.offset.unwrap_or(0),
+            after: query
+                .cursor
+                .map(|cursor| Cursor::decode(&cursor, sort))
+                .transpose()?,
+        })
+    }
+}
+
+impl MazeFilter {
+    // Filters, sorts and pages summaries held in memory, the same way the
+    // SQL stores do it in their queries.
+    pub fn apply(&self, mazes: impl IntoIterator<Item = MazeSummary>) -> Vec<MazeSummary> {
+        let mut mazes: Vec<MazeSummary> = mazes
+            .into_iter()
+            .filter(|maze| self.matches(maze))
+            .collect();
+        mazes.sort_by(|a, b| {
+            self.compare(
+                &SortKey::of(a, self.sort),
+                a.id,
+                &SortKey::of(b, self.sort),
+                b.id,
+            )
+        });
+
+        mazes
+            .into_iter()
+            .filter(|maze| match &self.after {
+                Some(after) => {
+                    self.compare(&SortKey::of(maze, self.sort), maze.id, &after.key, after.id)
+                        == Ordering::Greater
+                }
+                None => true,
+            })
+            .skip(self.offset)
+            .take(self.limit)
+            .collect()
+    }
+
+    fn matches(&self, maze: &MazeSummary) -> bool {
+        let name = match &self.name {
+            Some(name) => maze.name.to_lowercase().contains(&name.to_lowercase()),
+            None => true,
+        };
+        name && self.min_rows.is_none_or(|min| maze.rows >= min)
+            && self.max_rows.is_none_or(|max| maze.rows <= max)
+            && self.min_cols.is_none_or(|min| maze.cols >= min)
+            && self.max_cols.is_none_or(|max| maze.cols <= max)
+            && self
+                .min_difficulty
+                .is_none_or(|min| maze.difficulty.is_some_and(|d| d >= min))
+            && self
+                .max_difficulty
+                .is_none_or(|max| maze.difficulty.is_some_and(|d| d <= max))
+            && self
+                .min_dead_ends
+                .is_none_or(|min| maze.dead_ends.is_some_and(|d| d >= min))
+            && self
+                .max_dead_ends
+                .is_none_or(|max| maze.dead_ends.is_some_and(|d| d <= max))
+            && self
+                .created_after
+                .is_none_or(|after| maze.created_at >= after)
+            && self
+                .created_before
+                .is_none_or(|before| maze.created_at < before)
+    }
+
+    // Orders by the sort key with the id as a tie-breaker, in the
+    // requested direction.
+    fn compare(&self, a: &SortKey, a_id: Uuid, b: &SortKey, b_id: Uuid) -> Ordering {
+        let ordering = a.cmp(b).then(a_id.cmp(&b_id));
+        match self.order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortKey {
+    CreatedAt(DateTime<Utc>),
+    Name(String),
+    Size(i64),
+    // -1 for mazes without a difficulty, as in the SQL stores.
+    Difficulty(i64),
+}
+
+impl SortKey {
+    pub fn of(maze: &MazeSummary, sort: MazeSort) -> Self {
+        match sort {
+            MazeSort::CreatedAt => SortKey::CreatedAt(maze.created_at),
+            MazeSort::Name => SortKey::Name(maze.name.clone()),
+            MazeSort::Size => SortKey::Size((maze.rows * maze.cols) as i64),
+            MazeSort::Difficulty => {
+                SortKey::Difficulty(maze.difficulty.map_or(-1, |difficulty| difficulty as i64))
+            }
+        }
+    }
+}
+
+// Position of the last entry on a page. Encoded as hex so that it can be
+// passed back in a query string as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub key: SortKey,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn after(maze: &MazeSummary, sort: MazeSort) -> Self {
+        Self {
+            key: SortKey::of(maze, sort),
+            id: maze.id,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let (sort, value) = match &self.key {
+            SortKey::CreatedAt(created_at) => (MazeSort::CreatedAt, created_at.to_rfc3339()),
+            SortKey::Name(name) => (MazeSort::Name, name.clone()),
+            SortKey::Size(size) => (MazeSort::Size, size.to_string()),
+            SortKey::Difficulty(difficulty) => (MazeSort::Difficulty, difficulty.to_string()),
+        };
+        format!("{}\n{}\n{}", sort, self.id, value)
+            .bytes()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn decode(cursor: &str, sort: MazeSort) -> Result<Self, String> {
+        let invalid = || "invalid cursor".to_string();
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|i| {
+                cursor
+                    .get(i..i + 2)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        let mut parts = text.splitn(3, '\n');
+        let (Some(cursor_sort), Some(id), Some(value)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        if cursor_sort != sort.as_str() {
+            return Err(format!("cursor was made for sort={}", cursor_sort));
+        }
+
+        let id = id.parse().map_err(|_| invalid())?;
+        let key = match sort {
+            MazeSort::CreatedAt => SortKey::CreatedAt(
+                DateTime::parse_from_rfc3339(value)
+                    .map_err(|_| invalid())?
+                    .with_timezone(&Utc),
+            ),
+            MazeSort::Name => SortKey::Name(value.to_string()),
+            MazeSort::Size => SortKey::Size(value.parse().map_err(|_| invalid())?),
+            MazeSort::Difficulty => SortKey::Difficulty(value.parse().map_err(|_| invalid())?),
+        };
+        Ok(Self { key, id })
+    }
+}
+
+// Rows and columns as `Map::parse_from_string` counts them.
+pub fn dimensions(content: &str) -> (usize, usize) {
+    let rows = content.lines().count();
+    let cols = content
+        .lines()
+        .next()
+        .map_or(0, |line| line.chars().count());
+    (rows, cols)
+}
+
+// Difficulty and dead ends as the SQL stores keep them, both `None` when
+// the content does not parse.
+pub fn analysis(content: &str, topology: Topology) -> (Option<i32>, Option<i32>) {
+    let Ok(mut map) = Map::parse_from_string(content) else {
+        return (None, None);
+    };
+    map.topology = topology;
+    let (difficulty, dead_ends) = grade(&map);
+    (
+        difficulty.map(|difficulty| difficulty as i32),
+        Some(dead_ends as i32),
+    )
+}
+
+// Escapes `%`, `_` and `\` for a `LIKE ... ESCAPE '\'` pattern.
+pub fn like_pattern(name: &str) -> String {
+    let mut pattern = String::from("%");
+    for ch in name.chars() {
+        if matches!(ch, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+    pattern.push('%');
+    pattern
+}
+
+impl From<&MazeResponse> for MazeSummary {
+    fn from(maze: &MazeResponse) -> Self {
+        let (rows, cols) = dimensions(&maze.content);
+        let (difficulty, dead_ends) = analysis(&maze.content, maze.topology);
+        MazeSummary {
+            id: maze.id,
+            name: maze.name.clone(),
+            topology: maze.topology,
+            rows,
+            cols,
+            difficulty: difficulty.map(|difficulty| difficulty as u32),
+            dead_ends: dead_ends.map(|dead_ends| dead_ends as usize),
+            created_at: maze.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(crate) struct SummaryRow {
+    id: Uuid,
+    name: String,
+    topology: String,
+    rows: i32,
+    cols: i32,
+    difficulty: Option<i32>,
+    dead_ends: Option<i32>,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<SummaryRow> for MazeSummary {
+    type Error = sqlx::Error;
+
+    fn try_from(row: SummaryRow) -> Result<Self, Self::Error> {
+        Ok(MazeSummary {
+            id: row.id,
+            name: row.name,
+            topology: row.topology.parse().map_err(|e: String| sqlx::Error::Decode(e.into()))?,
+            rows: row.rows as usize,
+            cols: row.cols as usize,
+            difficulty: row.difficulty.map(|difficulty| difficulty as u32),
+            dead_ends: row.dead_ends.map(|dead_ends| dead_ends as usize),
+            created_at: row.created_at,
+        })
+    }
+}
+
+// Builds the list query shared by the SQL stores. `like` is the operator
+// used for the name search, so each database can pick a case-insensitive
+// one.
+pub(crate) fn list_query<'args, DB>(filter: &MazeFilter, like: &str) -> QueryBuilder<'args, DB>
+where
+    DB: Database,
+    i64: Encode<'args, DB> + Type<DB>,
+    String: Encode<'args, DB> + Type<DB>,
+    Uuid: Encode<'args, DB> + Type<DB>,
+    DateTime<Utc>: Encode<'args, DB> + Type<DB>,
+{
+    let mut query = QueryBuilder::new(
+        "SELECT id, name, topology, rows, cols, difficulty, dead_ends, created_at \
+         FROM mazes WHERE TRUE",
+    );
+
+    if let Some(name) = &filter.name {
+        query
+            .push(format!(" AND name {} ", like))
+            .push_bind(like_pattern(name))
+            .push(" ESCAPE '\\'");
+    }
+    let bounds = [
+        ("rows >= ", filter.min_rows),
+        ("rows <= ", filter.max_rows),
+        ("cols >= ", filter.min_cols),
+        ("cols <= ", filter.max_cols),
+        ("difficulty >= ", filter.min_difficulty.map(|min| min as usize)),
+        ("difficulty <= ", filter.max_difficulty.map(|max| max as usize)),
+        ("dead_ends >= ", filter.min_dead_ends),
+        ("dead_ends <= ", filter.max_dead_ends),
+    ];
+    for (condition, bound) in bounds {
+        if let Some(bound) = bound {
+            query.push(" AND ").push(condition).push_bind(bound as i64);
+        }
+    }
+    if let Some(after) = filter.created_after {
+        query.push(" AND created_at >= ").push_bind(after);
+    }
+    if let Some(before) = filter.created_before {
+        query.push(" AND created_at < ").push_bind(before);
+    }
+
+    let column = match filter.sort {
+        MazeSort::CreatedAt => "created_at",
+        MazeSort::Name => "name",
+        MazeSort::Size => "(rows * cols)",
+        MazeSort::Difficulty => "COALESCE(difficulty, -1)",
+    };
+    let (direction, comparison) = match filter.order {
+        SortOrder::Asc => ("ASC", ">"),
+        SortOrder::Desc => ("DESC", "<"),
+    };
+
+    if let Some(after) = &filter.after {
+        query.push(format!(" AND ({}, id) {} (", column, comparison));
+        match &after.key {
+            SortKey::CreatedAt(created_at) => query.push_bind(*created_at),
+            SortKey::Name(name) => query.push_bind(name.clone()),
+            SortKey::Size(size) => query.push_bind(*size),
+            SortKey::Difficulty(difficulty) => query.push_bind(*difficulty),
+        };
+        query.push(", ").push_bind(after.id).push(")");
+    }
+
+    query
+        .push(format!(
+            " ORDER BY {} {}, id {} LIMIT ",
+            column, direction, direction
+        ))
+        .push_bind(filter.limit as i64)
+        .push(" OFFSET ")
+        .push_bind(filter.offset as i64);
+    query
+}
@@ -1,41 +1,158 @@
-use crate::entities::MazeResponse;
-use sqlx::PgPool;
+use async_trait::async_trait;
+use sqlx::{PgPool, Postgres};
 use uuid::Uuid;
 
+use super::listing::{self, MazeFilter, SummaryRow};
+use super::solution_cache::{CachedSolution, SolutionKey};
+use super::store::{MazeStore, StoreError};
+use crate::domain::{DistanceField, Position, Topology};
+use crate::entities::{MazeResponse, MazeRevision, MazeSummary};
+
 #[derive(Clone)]
 pub struct MazeRepository {
     pool: PgPool,
 }
 
+struct MazeRow {
+    id: Uuid,
+    name: String,
+    content: String,
+    topology: String,
+    revision: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<MazeRow> for MazeResponse {
+    type Error = sqlx::Error;
+
+    fn try_from(row: MazeRow) -> Result<Self, Self::Error> {
+        Ok(MazeResponse {
+            id: row.id,
+            name: row.name,
+            content: row.content,
+            topology: row.topology.parse().map_err(|e: String| sqlx::Error::Decode(e.into()))?,
+            revision: row.revision as u32,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+struct RevisionRow {
+    revision: i32,
+    name: String,
+    content: String,
+    topology: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<RevisionRow> for MazeRevision {
+    type Error = sqlx::Error;
+
+    fn try_from(row: RevisionRow) -> Result<Self, Self::Error> {
+        Ok(MazeRevision {
+            revision: row.revision as u32,
+            name: row.name,
+            content: row.content,
+            topology: row.topology.parse().map_err(|e: String| sqlx::Error::Decode(e.into()))?,
+            created_at: row.created_at,
+        })
+    }
+}
+
 impl MazeRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
 
-    pub async fn create(&self, name: &str, content: &str) -> Result<MazeResponse, sqlx::Error> {
+    // Fills in the analysis columns for mazes saved before they existed and
+    // returns how many it filled. Content that does not parse is marked
+    // analyzed too, with both figures left NULL, so it is not retried.
+    pub async fn backfill_analysis(&self) -> Result<usize, StoreError> {
+        let mut analyzed = 0;
+        loop {
+            let pending = sqlx::query!(
+                r#"
+                SELECT id, content, topology, revision
+                FROM mazes
+                WHERE NOT analyzed
+                ORDER BY id
+                LIMIT $1
+                "#,
+                listing::BACKFILL_BATCH
+            )
+            .fetch_all(&self.pool)
+            .await?;
+            if pending.is_empty() {
+                return Ok(analyzed);
+            }
+
+            for maze in pending {
+                let topology: Topology = maze
+                    .topology
+                    .parse()
+                    .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+                let (difficulty, dead_ends) = listing::analysis(&maze.content, topology);
+                // The revision check leaves mazes edited meanwhile alone; the
+                // edit stored its own analysis and marked them analyzed.
+                sqlx::query!(
+                    r#"
+                    UPDATE mazes
+                    SET difficulty = $3, dead_ends = $4, analyzed = TRUE
+                    WHERE id = $1 AND revision = $2
+                    "#,
+                    maze.id,
+                    maze.revision,
+                    difficulty,
+                    dead_ends
+                )
+                .execute(&self.pool)
+                .await?;
+                analyzed += usize::from(dead_ends.is_some());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MazeStore for MazeRepository {
+    async fn create(
+        &self,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<MazeResponse, StoreError> {
         let id = Uuid::new_v4();
+        let (rows, cols) = listing::dimensions(content);
+        let (difficulty, dead_ends) = listing::analysis(content, topology);
         let maze = sqlx::query_as!(
-            MazeResponse,
+            MazeRow,
             r#"
-            INSERT INTO mazes (id, name, content)
-            VALUES ($1, $2, $3)
-            RETURNING id, name, content, created_at
+            INSERT INTO mazes (id, name, content, topology, rows, cols, difficulty, dead_ends)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, name, content, topology, revision, created_at, updated_at
             "#,
             id,
             name,
-            content
+            content,
+            topology.as_str(),
+            rows as i32,
+            cols as i32,
+            difficulty,
+            dead_ends
         )
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(maze)
+        Ok(maze.try_into()?)
     }
 
-    pub async fn get_by_id(&self, id: Uuid) -> Result<Option<MazeResponse>, sqlx::Error> {
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<MazeResponse>, StoreError> {
         let maze = sqlx::query_as!(
-            MazeResponse,
+            MazeRow,
             r#"
-            SELECT id, name, content, created_at
+            SELECT id, name, content, topology, revision, created_at, updated_at
             FROM mazes
             WHERE id = $1
             "#,
@@ -44,25 +161,137 @@ impl MazeRepository {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(maze)
+        Ok(maze.map(TryInto::try_into).transpose()?)
+    }
+
+    async fn list(&self, filter: &MazeFilter) -> Result<Vec<MazeSummary>, StoreError> {
+        let mazes: Vec<SummaryRow> = listing::list_query::<Postgres>(filter, "ILIKE")
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(mazes
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, sqlx::Error>>()?)
     }
 
-    pub async fn get_all(&self) -> Result<Vec<MazeResponse>, sqlx::Error> {
-        let mazes = sqlx::query_as!(
-            MazeResponse,
+    async fn update(
+        &self,
+        id: Uuid,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<Option<MazeResponse>, StoreError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Copying the current version and replacing it happen under one row
+        // lock, so concurrent updates cannot both claim the same revision.
+        let archived = sqlx::query!(
             r#"
-            SELECT id, name, content, created_at
+            INSERT INTO maze_revisions (maze_id, revision, name, content, topology, created_at)
+            SELECT id, revision, name, content, topology, updated_at
             FROM mazes
-            ORDER BY created_at DESC
-            "#
+            WHERE id = $1
+            FOR UPDATE
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if archived.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query!(
+            r#"
+            DELETE FROM maze_solutions
+            WHERE maze_id = $1
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM maze_distances
+            WHERE maze_id = $1
+            "#,
+            id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let (rows, cols) = listing::dimensions(content);
+        let (difficulty, dead_ends) = listing::analysis(content, topology);
+        let maze = sqlx::query_as!(
+            MazeRow,
+            r#"
+            UPDATE mazes
+            SET name = $2, content = $3, topology = $4, rows = $5, cols = $6,
+                difficulty = $7, dead_ends = $8, analyzed = TRUE,
+                revision = revision + 1, updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, name, content, topology, revision, created_at, updated_at
+            "#,
+            id,
+            name,
+            content,
+            topology.as_str(),
+            rows as i32,
+            cols as i32,
+            difficulty,
+            dead_ends
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(maze.try_into()?))
+    }
+
+    async fn revisions(&self, id: Uuid) -> Result<Vec<MazeRevision>, StoreError> {
+        let revisions = sqlx::query_as!(
+            RevisionRow,
+            r#"
+            SELECT revision, name, content, topology, created_at
+            FROM maze_revisions
+            WHERE maze_id = $1
+            ORDER BY revision DESC
+            "#,
+            id
         )
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(mazes)
+        Ok(revisions
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, sqlx::Error>>()?)
+    }
+
+    async fn get_revision(
+        &self,
+        id: Uuid,
+        revision: u32,
+    ) -> Result<Option<MazeRevision>, StoreError> {
+        let revision = sqlx::query_as!(
+            RevisionRow,
+            r#"
+            SELECT revision, name, content, topology, created_at
+            FROM maze_revisions
+            WHERE maze_id = $1 AND revision = $2
+            "#,
+            id,
+            revision as i32
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revision.map(TryInto::try_into).transpose()?)
     }
 
-    pub async fn delete(&self, id: Uuid) -> Result<bool, sqlx::Error> {
+    async fn delete(&self, id: Uuid) -> Result<bool, StoreError> {
         let result = sqlx::query!(
             r#"
             DELETE FROM mazes
@@ -75,4 +304,117 @@ impl MazeRepository {
 
         Ok(result.rows_affected() > 0)
     }
+
+    async fn cached_solution(
+        &self,
+        key: &SolutionKey,
+    ) -> Result<Option<CachedSolution>, StoreError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT solution
+            FROM maze_solutions
+            WHERE maze_id = $1 AND revision = $2
+                AND start_row = $3 AND start_col = $4 AND end_row = $5 AND end_col = $6
+                AND algorithm = $7 AND options = $8
+            "#,
+            key.maze_id,
+            key.revision as i32,
+            key.start.row as i32,
+            key.start.col as i32,
+            key.end.row as i32,
+            key.end.col as i32,
+            key.algorithm.as_str(),
+            key.options
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| CachedSolution::from_json(row.solution.as_deref()))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()).into())
+    }
+
+    async fn cache_solution(
+        &self,
+        key: &SolutionKey,
+        solution: &CachedSolution,
+    ) -> Result<(), StoreError> {
+        // Inserting for a maze that was deleted in the meantime trips the
+        // foreign key; nothing is lost by skipping it.
+        sqlx::query!(
+            r#"
+            INSERT INTO maze_solutions
+                (maze_id, revision, start_row, start_col, end_row, end_col, algorithm, options, solution)
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9
+            WHERE EXISTS (SELECT 1 FROM mazes WHERE id = $1 AND revision = $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            key.maze_id,
+            key.revision as i32,
+            key.start.row as i32,
+            key.start.col as i32,
+            key.end.row as i32,
+            key.end.col as i32,
+            key.algorithm.as_str(),
+            key.options,
+            solution.to_json()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn cached_distances(
+        &self,
+        id: Uuid,
+        revision: u32,
+        from: Position,
+    ) -> Result<Option<DistanceField>, StoreError> {
+        let row = sqlx::query!(
+            r#"
+            SELECT distances
+            FROM maze_distances
+            WHERE maze_id = $1 AND revision = $2 AND from_row = $3 AND from_col = $4
+            "#,
+            id,
+            revision as i32,
+            from.row as i32,
+            from.col as i32
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| serde_json::from_str(&row.distances))
+            .transpose()
+            .map(|distances| distances.map(|distances| DistanceField { from, distances }))
+            .map_err(|e| sqlx::Error::Decode(e.into()).into())
+    }
+
+    async fn cache_distances(
+        &self,
+        id: Uuid,
+        revision: u32,
+        field: &DistanceField,
+    ) -> Result<(), StoreError> {
+        let distances = serde_json::to_string(&field.distances)
+            .map_err(|e| sqlx::Error::Encode(e.into()))?;
+        sqlx::query!(
+            r#"
+            INSERT INTO maze_distances (maze_id, revision, from_row, from_col, distances)
+            SELECT $1, $2, $3, $4, $5
+            WHERE EXISTS (SELECT 1 FROM mazes WHERE id = $1 AND revision = $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            id,
+            revision as i32,
+            field.from.row as i32,
+            field.from.col as i32,
+            distances
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
 }
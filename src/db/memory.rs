@@ -0,0 +1,210 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use super::listing::MazeFilter;
+use super::solution_cache::{CachedSolution, SolutionKey};
+use super::store::{MazeStore, StoreError};
+use crate::domain::{DistanceField, Position, Topology};
+use crate::entities::{MazeResponse, MazeRevision, MazeSummary};
+
+// Maze id, revision and the cell measured from.
+type DistanceKey = (Uuid, u32, Position);
+
+// Keeps mazes in process memory; everything is lost on restart.
+#[derive(Clone, Default)]
+pub struct InMemoryMazeRepository {
+    mazes: Arc<RwLock<HashMap<Uuid, Stored>>>,
+    solutions: Arc<RwLock<HashMap<SolutionKey, CachedSolution>>>,
+    distances: Arc<RwLock<HashMap<DistanceKey, DistanceField>>>,
+}
+
+struct Stored {
+    maze: MazeResponse,
+    // Kept up to date with `maze` so listing does not analyze every maze.
+    summary: MazeSummary,
+    // Oldest first.
+    revisions: Vec<MazeRevision>,
+}
+
+impl InMemoryMazeRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn forget_cached(&self, id: Uuid) {
+        self.solutions
+            .write()
+            .unwrap()
+            .retain(|key, _| key.maze_id != id);
+        self.distances
+            .write()
+            .unwrap()
+            .retain(|(maze_id, _, _), _| *maze_id != id);
+    }
+}
+
+fn is_current(mazes: &HashMap<Uuid, Stored>, id: Uuid, revision: u32) -> bool {
+    mazes
+        .get(&id)
+        .is_some_and(|stored| stored.maze.revision == revision)
+}
+
+#[async_trait]
+impl MazeStore for InMemoryMazeRepository {
+    async fn create(
+        &self,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<MazeResponse, StoreError> {
+        let now = chrono::Utc::now();
+        let maze = MazeResponse {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            content: content.to_string(),
+            topology,
+            revision: 1,
+            created_at: now,
+            updated_at: now,
+        };
+        let stored = Stored {
+            summary: MazeSummary::from(&maze),
+            maze: maze.clone(),
+            revisions: Vec::new(),
+        };
+        self.mazes.write().unwrap().insert(maze.id, stored);
+        Ok(maze)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<MazeResponse>, StoreError> {
+        Ok(self
+            .mazes
+            .read()
+            .unwrap()
+            .get(&id)
+            .map(|stored| stored.maze.clone()))
+    }
+
+    async fn list(&self, filter: &MazeFilter) -> Result<Vec<MazeSummary>, StoreError> {
+        Ok(filter.apply(
+            self.mazes
+                .read()
+                .unwrap()
+                .values()
+                .map(|stored| stored.summary.clone()),
+        ))
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<Option<MazeResponse>, StoreError> {
+        let mut mazes = self.mazes.write().unwrap();
+        let Some(stored) = mazes.get_mut(&id) else {
+            return Ok(None);
+        };
+
+        let maze = &mut stored.maze;
+        stored.revisions.push(MazeRevision {
+            revision: maze.revision,
+            name: std::mem::replace(&mut maze.name, name.to_string()),
+            content: std::mem::replace(&mut maze.content, content.to_string()),
+            topology: std::mem::replace(&mut maze.topology, topology),
+            created_at: maze.updated_at,
+        });
+        maze.revision += 1;
+        maze.updated_at = chrono::Utc::now();
+        let maze = maze.clone();
+        stored.summary = MazeSummary::from(&maze);
+        self.forget_cached(id);
+        Ok(Some(maze))
+    }
+
+    async fn revisions(&self, id: Uuid) -> Result<Vec<MazeRevision>, StoreError> {
+        Ok(self
+            .mazes
+            .read()
+            .unwrap()
+            .get(&id)
+            .map(|stored| stored.revisions.iter().rev().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_revision(
+        &self,
+        id: Uuid,
+        revision: u32,
+    ) -> Result<Option<MazeRevision>, StoreError> {
+        Ok(self.mazes.read().unwrap().get(&id).and_then(|stored| {
+            stored
+                .revisions
+                .iter()
+                .find(|stored| stored.revision == revision)
+                .cloned()
+        }))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, StoreError> {
+        let deleted = self.mazes.write().unwrap().remove(&id).is_some();
+        self.forget_cached(id);
+        Ok(deleted)
+    }
+
+    async fn cached_solution(
+        &self,
+        key: &SolutionKey,
+    ) -> Result<Option<CachedSolution>, StoreError> {
+        Ok(self.solutions.read().unwrap().get(key).cloned())
+    }
+
+    async fn cache_solution(
+        &self,
+        key: &SolutionKey,
+        solution: &CachedSolution,
+    ) -> Result<(), StoreError> {
+        // Holding the maze lock keeps an update from slipping in between.
+        let mazes = self.mazes.read().unwrap();
+        if is_current(&mazes, key.maze_id, key.revision) {
+            self.solutions
+                .write()
+                .unwrap()
+                .insert(key.clone(), solution.clone());
+        }
+        Ok(())
+    }
+
+    async fn cached_distances(
+        &self,
+        id: Uuid,
+        revision: u32,
+        from: Position,
+    ) -> Result<Option<DistanceField>, StoreError> {
+        Ok(self
+            .distances
+            .read()
+            .unwrap()
+            .get(&(id, revision, from))
+            .cloned())
+    }
+
+    async fn cache_distances(
+        &self,
+        id: Uuid,
+        revision: u32,
+        field: &DistanceField,
+    ) -> Result<(), StoreError> {
+        let mazes = self.mazes.read().unwrap();
+        if is_current(&mazes, id, revision) {
+            self.distances
+                .write()
+                .unwrap()
+                .insert((id, revision, field.from), field.clone());
+        }
+        Ok(())
+    }
+}
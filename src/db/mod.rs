@@ -1,4 +1,17 @@
+pub mod files;
+pub mod listing;
 pub mod maze;
+pub mod memory;
+pub mod solution_cache;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod store;
 
+pub use files::{FileMazeRepository, ScanError};
+pub use listing::{Cursor, MazeFilter};
 pub use maze::MazeRepository;
-
+pub use memory::InMemoryMazeRepository;
+pub use solution_cache::{CachedSolution, SolutionKey};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteMazeRepository;
+pub use store::{MazeStore, StoreError};
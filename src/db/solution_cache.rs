@@ -0,0 +1,40 @@
+use uuid::Uuid;
+
+use crate::domain::{Algorithm, Position};
+use crate::entities::MazeSolutionResponse;
+
+// Everything a solution depends on. The revision is part of the key, so an
+// edited maze never serves a stale route even before its entries are dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SolutionKey {
+    pub maze_id: Uuid,
+    pub revision: u32,
+    pub start: Position,
+    pub end: Position,
+    pub algorithm: Algorithm,
+    // The remaining solver options serialized as JSON; for now the move set.
+    pub options: String,
+}
+
+#[derive(Debug, Clone)]
+pub enum CachedSolution {
+    Solved(MazeSolutionResponse),
+    // The search ran and found no path.
+    Unsolvable,
+}
+
+impl CachedSolution {
+    pub fn to_json(&self) -> Option<String> {
+        match self {
+            CachedSolution::Solved(solution) => serde_json::to_string(solution).ok(),
+            CachedSolution::Unsolvable => None,
+        }
+    }
+
+    pub fn from_json(json: Option<&str>) -> Result<Self, serde_json::Error> {
+        match json {
+            Some(json) => Ok(CachedSolution::Solved(serde_json::from_str(json)?)),
+            None => Ok(CachedSolution::Unsolvable),
+        }
+    }
+}
@@ -0,0 +1,420 @@
+use async_trait::async_trait;
+use sqlx::{Sqlite, SqlitePool};
+use uuid::Uuid;
+
+use super::listing::{self, MazeFilter, SummaryRow};
+use super::solution_cache::{CachedSolution, SolutionKey};
+use super::store::{MazeStore, StoreError};
+use crate::domain::{DistanceField, Position, Topology};
+use crate::entities::{MazeResponse, MazeRevision, MazeSummary};
+
+// SQLite has no server to check queries against at compile time, so this
+// backend uses runtime queries instead of the `query!` macros.
+#[derive(Clone)]
+pub struct SqliteMazeRepository {
+    pool: SqlitePool,
+}
+
+#[derive(sqlx::FromRow)]
+struct MazeRow {
+    id: Uuid,
+    name: String,
+    content: String,
+    topology: String,
+    revision: i32,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<MazeRow> for MazeResponse {
+    type Error = sqlx::Error;
+
+    fn try_from(row: MazeRow) -> Result<Self, Self::Error> {
+        Ok(MazeResponse {
+            id: row.id,
+            name: row.name,
+            content: row.content,
+            topology: row.topology.parse().map_err(|e: String| sqlx::Error::Decode(e.into()))?,
+            revision: row.revision as u32,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RevisionRow {
+    revision: i32,
+    name: String,
+    content: String,
+    topology: String,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl TryFrom<RevisionRow> for MazeRevision {
+    type Error = sqlx::Error;
+
+    fn try_from(row: RevisionRow) -> Result<Self, Self::Error> {
+        Ok(MazeRevision {
+            revision: row.revision as u32,
+            name: row.name,
+            content: row.content,
+            topology: row.topology.parse().map_err(|e: String| sqlx::Error::Decode(e.into()))?,
+            created_at: row.created_at,
+        })
+    }
+}
+
+impl SqliteMazeRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    // Fills in the analysis columns for mazes saved before they existed and
+    // returns how many it filled. Content that does not parse is marked
+    // analyzed too, with both figures left NULL, so it is not retried.
+    pub async fn backfill_analysis(&self) -> Result<usize, StoreError> {
+        let mut analyzed = 0;
+        loop {
+            let pending: Vec<(Uuid, String, String, i64)> = sqlx::query_as(
+                r#"
+                SELECT id, content, topology, revision
+                FROM mazes
+                WHERE NOT analyzed
+                ORDER BY id
+                LIMIT $1
+                "#,
+            )
+            .bind(listing::BACKFILL_BATCH)
+            .fetch_all(&self.pool)
+            .await?;
+            if pending.is_empty() {
+                return Ok(analyzed);
+            }
+
+            for (id, content, topology, revision) in pending {
+                let topology: Topology = topology
+                    .parse()
+                    .map_err(|e: String| sqlx::Error::Decode(e.into()))?;
+                let (difficulty, dead_ends) = listing::analysis(&content, topology);
+                // The revision check leaves mazes edited meanwhile alone; the
+                // edit stored its own analysis and marked them analyzed.
+                sqlx::query(
+                    r#"
+                    UPDATE mazes
+                    SET difficulty = $1, dead_ends = $2, analyzed = TRUE
+                    WHERE id = $3 AND revision = $4
+                    "#,
+                )
+                .bind(difficulty)
+                .bind(dead_ends)
+                .bind(id)
+                .bind(revision)
+                .execute(&self.pool)
+                .await?;
+                analyzed += usize::from(dead_ends.is_some());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl MazeStore for SqliteMazeRepository {
+    async fn create(
+        &self,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<MazeResponse, StoreError> {
+        let (rows, cols) = listing::dimensions(content);
+        let (difficulty, dead_ends) = listing::analysis(content, topology);
+        let now = chrono::Utc::now();
+        let maze: MazeRow = sqlx::query_as(
+            r#"
+            INSERT INTO mazes
+                (id, name, content, topology, created_at, updated_at, rows, cols, difficulty,
+                 dead_ends)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id, name, content, topology, revision, created_at, updated_at
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(name)
+        .bind(content)
+        .bind(topology.as_str())
+        .bind(now)
+        .bind(now)
+        .bind(rows as i64)
+        .bind(cols as i64)
+        .bind(difficulty)
+        .bind(dead_ends)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(maze.try_into()?)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<MazeResponse>, StoreError> {
+        let maze: Option<MazeRow> = sqlx::query_as(
+            r#"
+            SELECT id, name, content, topology, revision, created_at, updated_at
+            FROM mazes
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(maze.map(TryInto::try_into).transpose()?)
+    }
+
+    async fn list(&self, filter: &MazeFilter) -> Result<Vec<MazeSummary>, StoreError> {
+        let mazes: Vec<SummaryRow> = listing::list_query::<Sqlite>(filter, "LIKE")
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(mazes
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, sqlx::Error>>()?)
+    }
+
+    async fn update(
+        &self,
+        id: Uuid,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<Option<MazeResponse>, StoreError> {
+        // SQLite allows one writer at a time, so the transaction alone keeps
+        // concurrent updates from claiming the same revision.
+        let mut tx = self.pool.begin().await?;
+
+        let archived = sqlx::query(
+            r#"
+            INSERT INTO maze_revisions (maze_id, revision, name, content, topology, created_at)
+            SELECT id, revision, name, content, topology, updated_at
+            FROM mazes
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        if archived.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        sqlx::query(
+            r#"
+            DELETE FROM maze_solutions
+            WHERE maze_id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            DELETE FROM maze_distances
+            WHERE maze_id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        let (rows, cols) = listing::dimensions(content);
+        let (difficulty, dead_ends) = listing::analysis(content, topology);
+        let maze: MazeRow = sqlx::query_as(
+            r#"
+            UPDATE mazes
+            SET name = $1, content = $2, topology = $3, rows = $4, cols = $5,
+                difficulty = $6, dead_ends = $7, analyzed = TRUE,
+                revision = revision + 1, updated_at = $8
+            WHERE id = $9
+            RETURNING id, name, content, topology, revision, created_at, updated_at
+            "#,
+        )
+        .bind(name)
+        .bind(content)
+        .bind(topology.as_str())
+        .bind(rows as i64)
+        .bind(cols as i64)
+        .bind(difficulty)
+        .bind(dead_ends)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(maze.try_into()?))
+    }
+
+    async fn revisions(&self, id: Uuid) -> Result<Vec<MazeRevision>, StoreError> {
+        let revisions: Vec<RevisionRow> = sqlx::query_as(
+            r#"
+            SELECT revision, name, content, topology, created_at
+            FROM maze_revisions
+            WHERE maze_id = $1
+            ORDER BY revision DESC
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(revisions
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, sqlx::Error>>()?)
+    }
+
+    async fn get_revision(
+        &self,
+        id: Uuid,
+        revision: u32,
+    ) -> Result<Option<MazeRevision>, StoreError> {
+        let revision: Option<RevisionRow> = sqlx::query_as(
+            r#"
+            SELECT revision, name, content, topology, created_at
+            FROM maze_revisions
+            WHERE maze_id = $1 AND revision = $2
+            "#,
+        )
+        .bind(id)
+        .bind(revision as i64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(revision.map(TryInto::try_into).transpose()?)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, StoreError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM mazes
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn cached_solution(
+        &self,
+        key: &SolutionKey,
+    ) -> Result<Option<CachedSolution>, StoreError> {
+        let row: Option<(Option<String>,)> = sqlx::query_as(
+            r#"
+            SELECT solution
+            FROM maze_solutions
+            WHERE maze_id = $1 AND revision = $2
+                AND start_row = $3 AND start_col = $4 AND end_row = $5 AND end_col = $6
+                AND algorithm = $7 AND options = $8
+            "#,
+        )
+        .bind(key.maze_id)
+        .bind(key.revision as i64)
+        .bind(key.start.row as i64)
+        .bind(key.start.col as i64)
+        .bind(key.end.row as i64)
+        .bind(key.end.col as i64)
+        .bind(key.algorithm.as_str())
+        .bind(&key.options)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|(solution,)| CachedSolution::from_json(solution.as_deref()))
+            .transpose()
+            .map_err(|e| sqlx::Error::Decode(e.into()).into())
+    }
+
+    async fn cache_solution(
+        &self,
+        key: &SolutionKey,
+        solution: &CachedSolution,
+    ) -> Result<(), StoreError> {
+        // Inserting for a maze that was deleted in the meantime trips the
+        // foreign key; nothing is lost by skipping it.
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO maze_solutions
+                (maze_id, revision, start_row, start_col, end_row, end_col, algorithm, options, solution)
+            SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9
+            WHERE EXISTS (SELECT 1 FROM mazes WHERE id = $1 AND revision = $2)
+            "#,
+        )
+        .bind(key.maze_id)
+        .bind(key.revision as i64)
+        .bind(key.start.row as i64)
+        .bind(key.start.col as i64)
+        .bind(key.end.row as i64)
+        .bind(key.end.col as i64)
+        .bind(key.algorithm.as_str())
+        .bind(&key.options)
+        .bind(solution.to_json())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn cached_distances(
+        &self,
+        id: Uuid,
+        revision: u32,
+        from: Position,
+    ) -> Result<Option<DistanceField>, StoreError> {
+        let row: Option<(String,)> = sqlx::query_as(
+            r#"
+            SELECT distances
+            FROM maze_distances
+            WHERE maze_id = $1 AND revision = $2 AND from_row = $3 AND from_col = $4
+            "#,
+        )
+        .bind(id)
+        .bind(revision as i64)
+        .bind(from.row as i64)
+        .bind(from.col as i64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|(distances,)| serde_json::from_str(&distances))
+            .transpose()
+            .map(|distances| distances.map(|distances| DistanceField { from, distances }))
+            .map_err(|e| sqlx::Error::Decode(e.into()).into())
+    }
+
+    async fn cache_distances(
+        &self,
+        id: Uuid,
+        revision: u32,
+        field: &DistanceField,
+    ) -> Result<(), StoreError> {
+        let distances = serde_json::to_string(&field.distances)
+            .map_err(|e| sqlx::Error::Encode(e.into()))?;
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO maze_distances (maze_id, revision, from_row, from_col, distances)
+            SELECT $1, $2, $3, $4, $5
+            WHERE EXISTS (SELECT 1 FROM mazes WHERE id = $1 AND revision = $2)
+            "#,
+        )
+        .bind(id)
+        .bind(revision as i64)
+        .bind(field.from.row as i64)
+        .bind(field.from.col as i64)
+        .bind(distances)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
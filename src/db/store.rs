@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use std::fmt;
+use uuid::Uuid;
+
+use super::listing::MazeFilter;
+use super::solution_cache::{CachedSolution, SolutionKey};
+use crate::domain::{DistanceField, Position, Topology};
+use crate::entities::{MazeResponse, MazeRevision, MazeSummary};
+
+#[derive(Debug)]
+pub enum StoreError {
+    Database(sqlx::Error),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Database(e) => write!(f, "database error: {}", e),
+            StoreError::Io(e) => write!(f, "storage error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<sqlx::Error> for StoreError {
+    fn from(e: sqlx::Error) -> Self {
+        StoreError::Database(e)
+    }
+}
+
+impl From<std::io::Error> for StoreError {
+    fn from(e: std::io::Error) -> Self {
+        StoreError::Io(e)
+    }
+}
+
+// Where mazes are kept. The router is generic over this so the same
+// handlers run against Postgres or an in-memory map.
+#[async_trait]
+pub trait MazeStore: Clone + Send + Sync + 'static {
+    async fn create(
+        &self,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<MazeResponse, StoreError>;
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<MazeResponse>, StoreError>;
+
+    async fn list(&self, filter: &MazeFilter) -> Result<Vec<MazeSummary>, StoreError>;
+
+    // Replaces a maze and bumps its revision, keeping the replaced version
+    // in its history. `None` if there is no such maze.
+    async fn update(
+        &self,
+        id: Uuid,
+        name: &str,
+        content: &str,
+        topology: Topology,
+    ) -> Result<Option<MazeResponse>, StoreError>;
+
+    // Earlier versions of a maze, newest first.
+    async fn revisions(&self, id: Uuid) -> Result<Vec<MazeRevision>, StoreError>;
+
+    async fn get_revision(
+        &self,
+        id: Uuid,
+        revision: u32,
+    ) -> Result<Option<MazeRevision>, StoreError>;
+
+    // Returns whether a maze was deleted.
+    async fn delete(&self, id: Uuid) -> Result<bool, StoreError>;
+
+    // Stores without a cache keep these defaults, which never hit.
+    // Updating or deleting a maze drops its cached solutions and distances.
+    async fn cached_solution(
+        &self,
+        _key: &SolutionKey,
+    ) -> Result<Option<CachedSolution>, StoreError> {
+        Ok(None)
+    }
+
+    async fn cache_solution(
+        &self,
+        _key: &SolutionKey,
+        _solution: &CachedSolution,
+    ) -> Result<(), StoreError> {
+        Ok(())
+    }
+
+    // Distance fields are cached per revision in the same way.
+    async fn cached_distances(
+        &self,
+        _id: Uuid,
+        _revision: u32,
+        _from: Position,
+    ) -> Result<Option<DistanceField>, StoreError> {
+        Ok(None)
+    }
+
+    async fn cache_distances(
+        &self,
+        _id: Uuid,
+        _revision: u32,
+        _field: &DistanceField,
+    ) -> Result<(), StoreError> {
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::domain::map::{Cell, Map, Position};
+use crate::domain::pathfinding::DistanceField;
+
+// Solutions this long count as fully long for the difficulty score.
+const LONG_SOLUTION: f64 = 400.0;
+// Above this many open cells a distance field from every cell takes too
+// long, so the diameter is estimated from a few fields instead.
+const MAX_ALL_PAIRS_CELLS: usize = 1024;
+
+// Structural measures for comparing mazes. Two open cells are connected
+// when a step leads from one to the other either way, following the
+// maze's topology, moves and teleporters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MazeStats {
+    pub open_cells: usize,
+    // Open cells with a single neighbor, other than the start and end.
+    pub dead_ends: usize,
+    // Open cells with three or more neighbors.
+    pub junctions: usize,
+    pub components: usize,
+    // Independent cycles: edges minus cells plus components. Every open
+    // 2x2 block counts as one.
+    pub loops: usize,
+    // The longest shortest path between two open cells.
+    pub diameter: usize,
+    // Above `MAX_ALL_PAIRS_CELLS` open cells the diameter is estimated from
+    // two sweeps through the start's component, which is exact without loops.
+    pub diameter_exact: bool,
+    // Steps from start to end; `None` when the end cannot be reached.
+    pub solution_length: Option<usize>,
+    // Solution length over open cells.
+    pub solution_coverage: Option<f64>,
+    // 0 to 100, see `difficulty`; `None` for unsolvable mazes.
+    pub difficulty: Option<u32>,
+}
+
+pub fn analyze(map: &Map) -> MazeStats {
+    let (cells, mut stats) = measure(map);
+    (stats.diameter, stats.diameter_exact) = diameter(map, &cells);
+    stats
+}
+
+// Difficulty and dead ends alone, skipping the diameter and its search from
+// every cell. This is what the stores keep for listing.
+pub fn grade(map: &Map) -> (Option<u32>, usize) {
+    let (_, stats) = measure(map);
+    (stats.difficulty, stats.dead_ends)
+}
+
+// Everything but the diameter, which is left at zero; with the open cells.
+fn measure(map: &Map) -> (Vec<Position>, MazeStats) {
+    let links = links(map, &Cell::is_passable);
+    let cells: Vec<Position> = links.keys().copied().collect();
+
+    let degree = |pos: &Position| links[pos].len();
+    let dead_ends = cells
+        .iter()
+        .filter(|&&pos| degree(&pos) == 1 && pos != map.start && pos != map.end)
+        .count();
+    let junctions = cells.iter().filter(|pos| degree(pos) >= 3).count();
+    let edges = links.values().map(HashSet::len).sum::<usize>() / 2;
+    let components = components(&links);
+
+    let solution_length = DistanceField::compute(map, map.start)
+        .get(map.end)
+        .map(|distance| distance as usize);
+
+    let mut stats = MazeStats {
+        open_cells: cells.len(),
+        dead_ends,
+        junctions,
+        components,
+        loops: (edges + components).saturating_sub(links.len()),
+        diameter: 0,
+        diameter_exact: false,
+        solution_length,
+        solution_coverage: solution_length.map(|length| length as f64 / cells.len() as f64),
+        difficulty: None,
+    };
+    stats.difficulty = difficulty(map, &stats);
+    (cells, stats)
+}
+
+// Every cell `passable` accepts, with the cells one step connects it to
+// either way, following the maze's topology, moves and teleporters.
+pub(crate) fn links(
+    map: &Map,
+    passable: &dyn Fn(&Cell) -> bool,
+) -> HashMap<Position, HashSet<Position>> {
+    let mut links: HashMap<Position, HashSet<Position>> = HashMap::new();
+    for row in 0..map.rows {
+        for col in 0..map.cols {
+            if !passable(&map.grid[row][col]) {
+                continue;
+            }
+            let pos = Position { row, col };
+            links.entry(pos).or_default();
+            for &offset in map.moves.offsets(row) {
+                let Some(neighbor) = map.step_where(pos, offset, passable) else {
+                    continue;
+                };
+                if neighbor != pos {
+                    links.entry(pos).or_default().insert(neighbor);
+                    links.entry(neighbor).or_default().insert(pos);
+                }
+            }
+        }
+    }
+    links
+}
+
+fn components(links: &HashMap<Position, HashSet<Position>>) -> usize {
+    let mut seen = HashSet::new();
+    let mut components = 0;
+    for &origin in links.keys() {
+        if !seen.insert(origin) {
+            continue;
+        }
+        components += 1;
+        let mut stack = vec![origin];
+        while let Some(pos) = stack.pop() {
+            stack.extend(links[&pos].iter().filter(|&&next| seen.insert(next)));
+        }
+    }
+    components
+}
+
+fn diameter(map: &Map, cells: &[Position]) -> (usize, bool) {
+    let farthest = |from| DistanceField::compute(map, from).farthest();
+    if cells.len() <= MAX_ALL_PAIRS_CELLS {
+        let longest = cells
+            .iter()
+            .filter_map(|&pos| farthest(pos))
+            .map(|(_, distance)| distance)
+            .max();
+        return (longest.unwrap_or(0) as usize, true);
+    }
+
+    let swept = farthest(map.start)
+        .and_then(|(far, _)| farthest(far))
+        .map_or(0, |(_, distance)| distance);
+    (swept as usize, false)
+}
+
+// A weighted mix of how much the solution winds compared to a straight
+// line, how many dead ends and junctions there are per open cell, and
+// how long the solution is.
+fn difficulty(map: &Map, stats: &MazeStats) -> Option<u32> {
+    let length = stats.solution_length?;
+    if length == 0 {
+        return Some(0);
+    }
+
+    let straight = map
+        .topology
+        .displacements(map.start, map.end, map.rows, map.cols)
+        .into_iter()
+        .map(|(rows, cols)| map.moves.min_moves(rows, cols))
+        .min()
+        .unwrap_or(length);
+    let winding = 1.0 - (straight.min(length) as f64 / length as f64);
+    let density = |count: usize| (4.0 * count as f64 / stats.open_cells as f64).min(1.0);
+    let size = ((1.0 + length as f64).ln() / (1.0 + LONG_SOLUTION).ln()).min(1.0);
+
+    let score = 0.3 * winding
+        + 0.25 * density(stats.dead_ends)
+        + 0.2 * density(stats.junctions)
+        + 0.25 * size;
+    Some((score * 100.0).round() as u32)
+}
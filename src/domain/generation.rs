@@ -0,0 +1,254 @@
+mod aldous_broder;
+mod backtracker;
+mod eller;
+mod kruskal;
+mod prim;
+mod rng;
+mod wilson;
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use crate::domain::map::{Cell, Map, Position};
+use crate::domain::moves::MoveSet;
+use crate::domain::topology::Topology;
+
+pub use rng::Rng;
+
+// Up, right, down, left.
+const DIRECTIONS: [(i32, i32); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const RIGHT: usize = 1;
+const DOWN: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenerationAlgorithm {
+    #[default]
+    RecursiveBacktracker,
+    Prim,
+    Kruskal,
+    Eller,
+    Wilson,
+    AldousBroder,
+}
+
+impl GenerationAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GenerationAlgorithm::RecursiveBacktracker => "recursive_backtracker",
+            GenerationAlgorithm::Prim => "prim",
+            GenerationAlgorithm::Kruskal => "kruskal",
+            GenerationAlgorithm::Eller => "eller",
+            GenerationAlgorithm::Wilson => "wilson",
+            GenerationAlgorithm::AldousBroder => "aldous_broder",
+        }
+    }
+}
+
+impl fmt::Display for GenerationAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    // Start in the top-left cell, end in the bottom-right one.
+    #[default]
+    Corners,
+    Random,
+    // Start and end at the two ends of the longest corridor.
+    Farthest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerationOptions {
+    pub rows: usize,
+    pub cols: usize,
+    pub seed: u64,
+    pub toroidal: bool,
+    pub placement: Placement,
+}
+
+pub fn generate(
+    algorithm: GenerationAlgorithm,
+    options: &GenerationOptions,
+) -> Result<Map, String> {
+    if options.rows == 0 || options.cols == 0 {
+        return Err("Maze must have at least one row and one column".to_string());
+    }
+    if options.rows * options.cols < 2 {
+        return Err("Maze must have at least two cells".to_string());
+    }
+
+    let grid = CellGrid {
+        rows: options.rows,
+        cols: options.cols,
+        toroidal: options.toroidal,
+    };
+    let mut rng = Rng::new(options.seed);
+
+    let passages = match algorithm {
+        GenerationAlgorithm::RecursiveBacktracker => backtracker::carve(&grid, &mut rng),
+        GenerationAlgorithm::Prim => prim::carve(&grid, &mut rng),
+        GenerationAlgorithm::Kruskal => kruskal::carve(&grid, &mut rng),
+        GenerationAlgorithm::Eller => eller::carve(&grid, &mut rng),
+        GenerationAlgorithm::Wilson => wilson::carve(&grid, &mut rng),
+        GenerationAlgorithm::AldousBroder => aldous_broder::carve(&grid, &mut rng),
+    };
+
+    let (start, end) = place(&grid, &passages, options.placement, &mut rng);
+    Ok(grid.render(&passages, start, end))
+}
+
+// A passage is carved from `cell` through the wall in `direction`.
+#[derive(Debug, Clone, Copy)]
+struct Passage {
+    cell: usize,
+    direction: usize,
+}
+
+// The logical maze: `rows * cols` cells separated by walls. Cell `(r, c)`
+// ends up at `(2r + 1, 2c + 1)` on the rendered map.
+struct CellGrid {
+    rows: usize,
+    cols: usize,
+    toroidal: bool,
+}
+
+impl CellGrid {
+    fn len(&self) -> usize {
+        self.rows * self.cols
+    }
+
+    fn neighbor(&self, cell: usize, direction: usize) -> Option<usize> {
+        let (dr, dc) = DIRECTIONS[direction];
+        let row = (cell / self.cols) as i32 + dr;
+        let col = (cell % self.cols) as i32 + dc;
+
+        let (row, col) = if self.toroidal {
+            (
+                row.rem_euclid(self.rows as i32),
+                col.rem_euclid(self.cols as i32),
+            )
+        } else if row < 0 || col < 0 || row >= self.rows as i32 || col >= self.cols as i32 {
+            return None;
+        } else {
+            (row, col)
+        };
+
+        let neighbor = row as usize * self.cols + col as usize;
+        (neighbor != cell).then_some(neighbor)
+    }
+
+    fn neighbors(&self, cell: usize) -> Vec<(usize, usize)> {
+        (0..DIRECTIONS.len())
+            .filter_map(|direction| self.neighbor(cell, direction).map(|n| (direction, n)))
+            .collect()
+    }
+
+    fn adjacency(&self, passages: &[Passage]) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![Vec::new(); self.len()];
+        for passage in passages {
+            if let Some(neighbor) = self.neighbor(passage.cell, passage.direction) {
+                adjacency[passage.cell].push(neighbor);
+                adjacency[neighbor].push(passage.cell);
+            }
+        }
+        adjacency
+    }
+
+    fn position(&self, cell: usize) -> Position {
+        Position {
+            row: 2 * (cell / self.cols) + 1,
+            col: 2 * (cell % self.cols) + 1,
+        }
+    }
+
+    fn render(&self, passages: &[Passage], start: usize, end: usize) -> Map {
+        // Toroidal mazes drop the outer border so that the wrap-around
+        // walls are shared between opposite edges.
+        let (rows, cols) = if self.toroidal {
+            (2 * self.rows, 2 * self.cols)
+        } else {
+            (2 * self.rows + 1, 2 * self.cols + 1)
+        };
+
+        let mut grid = vec![vec![Cell::Wall; cols]; rows];
+        for cell in 0..self.len() {
+            let pos = self.position(cell);
+            grid[pos.row][pos.col] = Cell::Empty;
+        }
+        for passage in passages {
+            let pos = self.position(passage.cell);
+            let (dr, dc) = DIRECTIONS[passage.direction];
+            let row = (pos.row as i32 + dr).rem_euclid(rows as i32) as usize;
+            let col = (pos.col as i32 + dc).rem_euclid(cols as i32) as usize;
+            grid[row][col] = Cell::Empty;
+        }
+
+        let start = self.position(start);
+        let end = self.position(end);
+        grid[start.row][start.col] = Cell::Start;
+        grid[end.row][end.col] = Cell::End;
+
+        let topology = if self.toroidal {
+            Topology::Torus
+        } else {
+            Topology::Bounded
+        };
+
+        Map {
+            grid,
+            start,
+            end,
+            rows,
+            cols,
+            topology,
+            moves: MoveSet::default(),
+            portals: HashMap::new(),
+        }
+    }
+}
+
+fn place(
+    grid: &CellGrid,
+    passages: &[Passage],
+    placement: Placement,
+    rng: &mut Rng,
+) -> (usize, usize) {
+    match placement {
+        Placement::Corners => (0, grid.len() - 1),
+        Placement::Random => {
+            let start = rng.below(grid.len());
+            let end = (start + 1 + rng.below(grid.len() - 1)) % grid.len();
+            (start, end)
+        }
+        Placement::Farthest => {
+            let adjacency = grid.adjacency(passages);
+            let start = farthest_from(&adjacency, 0);
+            (start, farthest_from(&adjacency, start))
+        }
+    }
+}
+
+fn farthest_from(adjacency: &[Vec<usize>], origin: usize) -> usize {
+    let mut distance = vec![usize::MAX; adjacency.len()];
+    let mut queue = VecDeque::from([origin]);
+    let mut last = origin;
+    distance[origin] = 0;
+
+    while let Some(cell) = queue.pop_front() {
+        last = cell;
+        for &neighbor in &adjacency[cell] {
+            if distance[neighbor] == usize::MAX {
+                distance[neighbor] = distance[cell] + 1;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    last
+}
@@ -0,0 +1,22 @@
+use super::{CellGrid, Passage, Rng};
+
+pub(super) fn carve(grid: &CellGrid, rng: &mut Rng) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut visited = vec![false; grid.len()];
+    let mut cell = rng.below(grid.len());
+    let mut remaining = grid.len() - 1;
+    visited[cell] = true;
+
+    while remaining > 0 {
+        let neighbors = grid.neighbors(cell);
+        let (direction, neighbor) = neighbors[rng.below(neighbors.len())];
+        if !visited[neighbor] {
+            passages.push(Passage { cell, direction });
+            visited[neighbor] = true;
+            remaining -= 1;
+        }
+        cell = neighbor;
+    }
+
+    passages
+}
@@ -0,0 +1,29 @@
+use super::{CellGrid, Passage, Rng};
+
+pub(super) fn carve(grid: &CellGrid, rng: &mut Rng) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut visited = vec![false; grid.len()];
+    let origin = rng.below(grid.len());
+    let mut stack = vec![origin];
+    visited[origin] = true;
+
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<_> = grid
+            .neighbors(cell)
+            .into_iter()
+            .filter(|&(_, neighbor)| !visited[neighbor])
+            .collect();
+
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (direction, neighbor) = unvisited[rng.below(unvisited.len())];
+        passages.push(Passage { cell, direction });
+        visited[neighbor] = true;
+        stack.push(neighbor);
+    }
+
+    passages
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use super::{CellGrid, Passage, Rng, DOWN, RIGHT};
+
+// Eller's algorithm works one row at a time, so the vertical wrap of a
+// toroidal grid is never carved; the horizontal wrap is.
+pub(super) fn carve(grid: &CellGrid, rng: &mut Rng) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut carried: Vec<Option<usize>> = vec![None; grid.cols];
+    let mut next_set = 0;
+
+    for row in 0..grid.rows {
+        let last_row = row + 1 == grid.rows;
+        let base = row * grid.cols;
+
+        let mut sets: Vec<usize> = carried
+            .iter()
+            .map(|set| {
+                set.unwrap_or_else(|| {
+                    next_set += 1;
+                    next_set
+                })
+            })
+            .collect();
+
+        for col in 0..grid.cols {
+            let cell = base + col;
+            let Some(neighbor) = grid.neighbor(cell, RIGHT) else {
+                continue;
+            };
+            let neighbor_col = neighbor - base;
+            if sets[col] == sets[neighbor_col] || !(last_row || rng.chance()) {
+                continue;
+            }
+
+            passages.push(Passage {
+                cell,
+                direction: RIGHT,
+            });
+            let (keep, merge) = (sets[col], sets[neighbor_col]);
+            for set in sets.iter_mut().filter(|set| **set == merge) {
+                *set = keep;
+            }
+        }
+
+        if last_row {
+            break;
+        }
+
+        // Every set has to reach the next row at least once, otherwise it
+        // would end up disconnected from the rest of the maze.
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (col, &set) in sets.iter().enumerate() {
+            members.entry(set).or_default().push(col);
+        }
+
+        carried = vec![None; grid.cols];
+        for (set, mut cols) in members {
+            rng.shuffle(&mut cols);
+            let extend = 1 + rng.below(cols.len());
+            for &col in &cols[..extend] {
+                passages.push(Passage {
+                    cell: base + col,
+                    direction: DOWN,
+                });
+                carried[col] = Some(set);
+            }
+        }
+    }
+
+    passages
+}
@@ -0,0 +1,49 @@
+use super::{CellGrid, Passage, Rng, DOWN, RIGHT};
+
+pub(super) fn carve(grid: &CellGrid, rng: &mut Rng) -> Vec<Passage> {
+    let mut walls: Vec<Passage> = (0..grid.len())
+        .flat_map(|cell| [RIGHT, DOWN].map(|direction| Passage { cell, direction }))
+        .filter(|wall| grid.neighbor(wall.cell, wall.direction).is_some())
+        .collect();
+    rng.shuffle(&mut walls);
+
+    let mut sets = DisjointSets::new(grid.len());
+    walls
+        .into_iter()
+        .filter(|wall| {
+            let neighbor = grid
+                .neighbor(wall.cell, wall.direction)
+                .unwrap_or(wall.cell);
+            sets.union(wall.cell, neighbor)
+        })
+        .collect()
+}
+
+struct DisjointSets {
+    parent: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut item: usize) -> usize {
+        while self.parent[item] != item {
+            self.parent[item] = self.parent[self.parent[item]];
+            item = self.parent[item];
+        }
+        item
+    }
+
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        self.parent[b] = a;
+        true
+    }
+}
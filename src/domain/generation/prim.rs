@@ -0,0 +1,42 @@
+use super::{CellGrid, Passage, Rng};
+
+pub(super) fn carve(grid: &CellGrid, rng: &mut Rng) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut in_maze = vec![false; grid.len()];
+    let mut frontier = Vec::new();
+
+    let origin = rng.below(grid.len());
+    in_maze[origin] = true;
+    frontier.extend(
+        grid.neighbors(origin)
+            .into_iter()
+            .map(|(direction, _)| Passage {
+                cell: origin,
+                direction,
+            }),
+    );
+
+    while !frontier.is_empty() {
+        let passage = frontier.swap_remove(rng.below(frontier.len()));
+        let Some(target) = grid.neighbor(passage.cell, passage.direction) else {
+            continue;
+        };
+        if in_maze[target] {
+            continue;
+        }
+
+        passages.push(passage);
+        in_maze[target] = true;
+        frontier.extend(
+            grid.neighbors(target)
+                .into_iter()
+                .filter(|&(_, neighbor)| !in_maze[neighbor])
+                .map(|(direction, _)| Passage {
+                    cell: target,
+                    direction,
+                }),
+        );
+    }
+
+    passages
+}
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use super::{CellGrid, Passage, Rng};
+
+pub(super) fn carve(grid: &CellGrid, rng: &mut Rng) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut in_maze = vec![false; grid.len()];
+    in_maze[rng.below(grid.len())] = true;
+
+    let mut pending: Vec<usize> = (0..grid.len()).collect();
+    rng.shuffle(&mut pending);
+
+    for origin in pending {
+        if in_maze[origin] {
+            continue;
+        }
+
+        // Random walk until the maze is hit, remembering only the last exit
+        // taken from each cell; that erases any loops the walk made.
+        let mut exits = HashMap::new();
+        let mut cell = origin;
+        while !in_maze[cell] {
+            let neighbors = grid.neighbors(cell);
+            let (direction, neighbor) = neighbors[rng.below(neighbors.len())];
+            exits.insert(cell, direction);
+            cell = neighbor;
+        }
+
+        let mut cell = origin;
+        while !in_maze[cell] {
+            let direction = exits[&cell];
+            in_maze[cell] = true;
+            passages.push(Passage { cell, direction });
+            cell = grid.neighbor(cell, direction).unwrap_or(cell);
+        }
+    }
+
+    passages
+}
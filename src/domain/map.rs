@@ -1,6 +1,11 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::io::{self, BufRead};
 use std::fmt;
 
+use crate::domain::moves::MoveSet;
+use crate::domain::topology::Topology;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Cell {
     Wall,
@@ -8,9 +13,38 @@ pub enum Cell {
     Start,
     End,
     Path,
+    Road,
+    Mud,
+    Water,
+    Key(char),
+    Door(char),
+    Teleporter(char),
+}
+
+impl Cell {
+    pub fn cost(&self) -> Option<u32> {
+        match self {
+            Cell::Wall => None,
+            Cell::Road => Some(1),
+            Cell::Mud => Some(4),
+            Cell::Water => Some(8),
+            Cell::Empty
+            | Cell::Start
+            | Cell::End
+            | Cell::Path
+            | Cell::Key(_)
+            | Cell::Door(_)
+            | Cell::Teleporter(_) => Some(2),
+        }
+    }
+
+    // Doors count as walls unless the caller tracks which keys are held.
+    pub fn is_passable(&self) -> bool {
+        !matches!(self, Cell::Wall | Cell::Door(_))
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Position {
     pub row: usize,
     pub col: usize,
@@ -22,6 +56,10 @@ pub struct Map {
     pub end: Position,
     pub rows: usize,
     pub cols: usize,
+    pub topology: Topology,
+    pub moves: MoveSet,
+    // Each teleporter cell mapped to its partner.
+    pub portals: HashMap<Position, Position>,
 }
 
 impl Map {
@@ -51,9 +89,13 @@ impl Map {
             end: Position { row: 0, col: 0 },
             rows: 0,
             cols: 0,
+            topology: Topology::default(),
+            moves: MoveSet::default(),
+            portals: HashMap::new(),
         };
         let mut start_found = false;
         let mut end_found = false;
+        let mut teleporters: BTreeMap<char, Vec<Position>> = BTreeMap::new();
 
         let expected_cols = lines[0].chars().count();
         if expected_cols == 0 {
@@ -76,6 +118,17 @@ impl Map {
                 let cell = match ch {
                     '#' => Cell::Wall,
                     ' ' => Cell::Empty,
+                    '=' => Cell::Road,
+                    '%' => Cell::Mud,
+                    '~' => Cell::Water,
+                    // 'i' and 'O' already mark start and end, so neither
+                    // they nor their other-case partners are keys or doors.
+                    'a'..='z' if ch != 'i' && ch != 'o' => Cell::Key(ch),
+                    'A'..='Z' if ch != 'I' && ch != 'O' => Cell::Door(ch),
+                    '0'..='9' => {
+                        teleporters.entry(ch).or_default().push(Position { row, col });
+                        Cell::Teleporter(ch)
+                    }
                     'i' => {
                         if start_found {
                             return Err("Multiple start positions found".to_string());
@@ -106,6 +159,30 @@ impl Map {
             return Err("End position 'O' not found".to_string());
         }
 
+        for (ch, positions) in teleporters {
+            match positions[..] {
+                [a, b] => {
+                    map.portals.insert(a, b);
+                    map.portals.insert(b, a);
+                }
+                [pos] => {
+                    return Err(format!(
+                        "Teleporter '{}' at row {}, col {} has no partner",
+                        ch,
+                        pos.row + 1,
+                        pos.col + 1
+                    ))
+                }
+                _ => {
+                    return Err(format!(
+                        "Teleporter '{}' appears {} times, expected exactly 2",
+                        ch,
+                        positions.len()
+                    ))
+                }
+            }
+        }
+
         map.rows = map.grid.len();
         map.cols = expected_cols;
 
@@ -113,22 +190,56 @@ impl Map {
     }
 
     pub fn get_neighbors(&self, pos: Position) -> Vec<Position> {
-        let mut neighbors = Vec::new();
-        
-        let directions = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-        
-        for (dr, dc) in directions.iter() {
-            let new_row = (pos.row as i32 + dr).rem_euclid(self.rows as i32) as usize;
-            let new_col = (pos.col as i32 + dc).rem_euclid(self.cols as i32) as usize;
-            
-            let new_pos = Position { row: new_row, col: new_col };
-            
-            if self.grid[new_pos.row][new_pos.col] != Cell::Wall {
-                neighbors.push(new_pos);
+        self.moves
+            .offsets(pos.row)
+            .iter()
+            .filter_map(|&offset| self.step(pos, offset))
+            .collect()
+    }
+
+    pub fn step(&self, pos: Position, offset: (i32, i32)) -> Option<Position> {
+        self.step_where(pos, offset, &Cell::is_passable)
+    }
+
+    pub fn step_where(
+        &self,
+        pos: Position,
+        (dr, dc): (i32, i32),
+        passable: &dyn Fn(&Cell) -> bool,
+    ) -> Option<Position> {
+        let new_pos = self.topology.wrap(
+            pos.row as i64 + dr as i64,
+            pos.col as i64 + dc as i64,
+            self.rows,
+            self.cols,
+        )?;
+
+        if !passable(&self.grid[new_pos.row][new_pos.col]) {
+            return None;
+        }
+
+        let diagonal = dr.abs() == 1 && dc.abs() == 1;
+        if diagonal
+            && self.moves.blocks_corners()
+            && (self.step_where(pos, (dr, 0), passable).is_none()
+                || self.step_where(pos, (0, dc), passable).is_none())
+        {
+            return None;
+        }
+
+        // Stepping onto a teleporter lands on its partner, unless the
+        // teleporter is the goal itself.
+        if new_pos != self.end {
+            if let Some(&exit) = self.portals.get(&new_pos) {
+                return Some(exit);
             }
         }
-        
-        neighbors
+
+        Some(new_pos)
+    }
+
+    pub fn cost(&self, pos: Position) -> Option<u32> {
+        self.grid[pos.row][pos.col].cost()
     }
 
     pub fn mark_path(&mut self, path: &[Position]) {
@@ -151,6 +262,12 @@ impl fmt::Display for Map {
                     Cell::Start => 'i',
                     Cell::End => 'O',
                     Cell::Path => '.',
+                    Cell::Road => '=',
+                    Cell::Mud => '%',
+                    Cell::Water => '~',
+                    Cell::Key(key) => *key,
+                    Cell::Door(door) => *door,
+                    Cell::Teleporter(teleporter) => *teleporter,
                 };
                 write!(f, "{}", ch)?;
             }
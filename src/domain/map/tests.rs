@@ -13,6 +13,9 @@ fn test_map_parsing() {
         end: Position { row: 2, col: 3 },
         rows: 4,
         cols: 7,
+        topology: Topology::Torus,
+        moves: MoveSet::Orthogonal,
+        portals: HashMap::new(),
     };
 
     assert_eq!(map.start, Position { row: 1, col: 4 });
@@ -31,6 +34,9 @@ fn test_toroidal_neighbors() {
         end: Position { row: 0, col: 0 },
         rows: 3,
         cols: 3,
+        topology: Topology::Torus,
+        moves: MoveSet::Orthogonal,
+        portals: HashMap::new(),
     };
 
     let neighbors = map.get_neighbors(Position { row: 0, col: 0 });
@@ -1,6 +1,24 @@
+pub mod analysis;
+pub mod generation;
 pub mod map;
+pub mod moves;
 pub mod pathfinding;
+pub mod play;
+pub mod render;
+pub mod route;
+pub mod topology;
+pub mod validation;
 
+pub use analysis::{analyze, grade, MazeStats};
+pub use generation::{generate, GenerationAlgorithm, GenerationOptions, Placement};
 pub use map::{Cell, Map, Position};
-pub use pathfinding::find_path;
-
+pub use moves::MoveSet;
+pub use pathfinding::{
+    a_star, collected_keys, count_shortest_paths, dijkstra, enumerate_shortest_paths,
+    find_nearest_goal, find_path, solve_with_keys, Algorithm, DistanceField, SearchResult, Solver,
+    TraceFrame,
+};
+pub use render::{render, Color, Overlay, RenderFormat, RenderOptions};
+pub use route::Step;
+pub use topology::Topology;
+pub use validation::{validate, ValidationPolicy};
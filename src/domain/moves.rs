@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::topology::Topology;
+
+const ORTHOGONAL: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+const DIAGONAL: [(i32, i32); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (-1, 1),
+    (1, -1),
+    (1, 1),
+];
+const KNIGHT: [(i32, i32); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+// Hex cells in "odd-r" layout: odd rows are shifted half a cell to the right.
+const HEX_EVEN_ROW: [(i32, i32); 6] = [(-1, -1), (-1, 0), (0, -1), (0, 1), (1, -1), (1, 0)];
+const HEX_ODD_ROW: [(i32, i32); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, 0), (1, 1)];
+
+const MAX_CUSTOM_OFFSETS: usize = 64;
+// Furthest a custom offset may jump along either axis.
+const MAX_OFFSET_DISTANCE: u32 = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MoveSet {
+    #[default]
+    Orthogonal,
+    Diagonal {
+        // Whether a diagonal step may squeeze between two walls that touch
+        // at a corner.
+        #[serde(default)]
+        corner_cutting: bool,
+    },
+    Hex,
+    Knight,
+    Custom {
+        offsets: Vec<(i32, i32)>,
+    },
+}
+
+impl MoveSet {
+    pub fn offsets(&self, row: usize) -> &[(i32, i32)] {
+        match self {
+            MoveSet::Orthogonal => &ORTHOGONAL,
+            MoveSet::Diagonal { .. } => &DIAGONAL,
+            MoveSet::Hex if row.is_multiple_of(2) => &HEX_EVEN_ROW,
+            MoveSet::Hex => &HEX_ODD_ROW,
+            MoveSet::Knight => &KNIGHT,
+            MoveSet::Custom { offsets } => offsets,
+        }
+    }
+
+    pub fn blocks_corners(&self) -> bool {
+        matches!(
+            self,
+            MoveSet::Diagonal {
+                corner_cutting: false
+            }
+        )
+    }
+
+    // Whether every move can be undone by another move, which lets searches
+    // walk the graph backwards using the same neighbors. Crossing a seam that
+    // flips the rows turns a move's row direction around, so on those
+    // topologies only moves within a column are trusted to undo each other.
+    pub fn is_reversible(&self, topology: Topology) -> bool {
+        let crosses_columns = (0..2).any(|row| self.offsets(row).iter().any(|&(_, dc)| dc != 0));
+        if topology.flips_rows() && crosses_columns {
+            return false;
+        }
+        match self {
+            MoveSet::Orthogonal | MoveSet::Diagonal { .. } | MoveSet::Knight => true,
+            MoveSet::Hex => false,
+            MoveSet::Custom { offsets } => offsets
+                .iter()
+                .all(|&(dr, dc)| offsets.contains(&(-dr, -dc))),
+        }
+    }
+
+    // Fewest moves needed to cover the given row and column distance on an
+    // empty grid.
+    pub fn min_moves(&self, rows: usize, cols: usize) -> usize {
+        let offsets: Vec<(i32, i32)> = match self {
+            MoveSet::Hex => HEX_EVEN_ROW.iter().chain(&HEX_ODD_ROW).copied().collect(),
+            _ => self.offsets(0).to_vec(),
+        };
+
+        let reach = |length: fn(usize, usize) -> usize| {
+            offsets
+                .iter()
+                .map(|&(dr, dc)| length(dr.unsigned_abs() as usize, dc.unsigned_abs() as usize))
+                .max()
+                .unwrap_or(0)
+        };
+        let manhattan = |r: usize, c: usize| r + c;
+        let chebyshev = |r: usize, c: usize| r.max(c);
+
+        let (manhattan_reach, chebyshev_reach) = (reach(manhattan), reach(chebyshev));
+        if manhattan_reach == 0 {
+            return 0;
+        }
+
+        manhattan(rows, cols)
+            .div_ceil(manhattan_reach)
+            .max(chebyshev(rows, cols).div_ceil(chebyshev_reach))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let MoveSet::Custom { offsets } = self else {
+            return Ok(());
+        };
+
+        if offsets.is_empty() {
+            return Err("Custom move set has no offsets".to_string());
+        }
+        if offsets.len() > MAX_CUSTOM_OFFSETS {
+            return Err(format!(
+                "Custom move set has {} offsets, at most {} are allowed",
+                offsets.len(),
+                MAX_CUSTOM_OFFSETS
+            ));
+        }
+        if offsets.contains(&(0, 0)) {
+            return Err("Custom move set contains the zero offset".to_string());
+        }
+        if let Some(&(dr, dc)) = offsets
+            .iter()
+            .find(|(dr, dc)| dr.unsigned_abs().max(dc.unsigned_abs()) > MAX_OFFSET_DISTANCE)
+        {
+            return Err(format!(
+                "Custom move set offset ({}, {}) is too long, at most {} rows or columns \
+                 are allowed",
+                dr, dc, MAX_OFFSET_DISTANCE
+            ));
+        }
+        Ok(())
+    }
+}
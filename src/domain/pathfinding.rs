@@ -1,36 +1,104 @@
-use std::collections::{VecDeque, HashSet, HashMap};
+mod bfs;
+mod bidirectional;
+mod dfs;
+mod distances;
+mod keys;
+mod shortest_paths;
+mod trace;
+mod wall_follower;
+mod weighted;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
 use crate::domain::map::{Map, Position};
 
-pub fn find_path(map: &Map) -> Option<Vec<Position>> {
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-    let mut parent = HashMap::new();
-    
-    queue.push_back(map.start);
-    visited.insert(map.start);
-    
-    while let Some(current) = queue.pop_front() {
-        if current == map.end {
-            let mut path = Vec::new();
-            let mut pos = current;
-            
-            while pos != map.start {
-                path.push(pos);
-                pos = parent[&pos];
-            }
-            path.push(map.start);
-            path.reverse();
-            return Some(path);
+pub use bfs::Bfs;
+pub use bidirectional::BidirectionalBfs;
+pub use dfs::Dfs;
+pub use distances::DistanceField;
+pub use keys::{check_keys, collected_keys, solve_with_keys, KeyCollector, MAX_KEY_STATES};
+pub use shortest_paths::{count_shortest_paths, enumerate_shortest_paths, find_nearest_goal};
+pub use trace::{can_trace, check_trace, trace, TraceFrame, MAX_FRONTIER, MAX_TRACE_CELLS};
+pub use wall_follower::WallFollower;
+pub use weighted::{a_star, dijkstra, AStar, Dijkstra};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchResult {
+    pub path: Option<Vec<Position>>,
+    // Number of cells the solver expanded before it stopped.
+    pub explored: usize,
+}
+
+pub trait Solver {
+    fn search(&self, map: &Map) -> SearchResult;
+
+    fn solve(&self, map: &Map) -> Option<Vec<Position>> {
+        self.search(map).path
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Algorithm {
+    #[default]
+    Bfs,
+    Dfs,
+    BidirectionalBfs,
+    AStar,
+    Dijkstra,
+    WallFollower,
+    KeyCollector,
+}
+
+impl Algorithm {
+    pub fn solver(&self) -> Box<dyn Solver> {
+        match self {
+            Algorithm::Bfs => Box::new(Bfs),
+            Algorithm::Dfs => Box::new(Dfs),
+            Algorithm::BidirectionalBfs => Box::new(BidirectionalBfs),
+            Algorithm::AStar => Box::new(AStar),
+            Algorithm::Dijkstra => Box::new(Dijkstra),
+            Algorithm::WallFollower => Box::new(WallFollower),
+            Algorithm::KeyCollector => Box::new(KeyCollector),
         }
-        
-        for neighbor in map.get_neighbors(current) {
-            if !visited.contains(&neighbor) {
-                visited.insert(neighbor);
-                parent.insert(neighbor, current);
-                queue.push_back(neighbor);
-            }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Algorithm::Bfs => "bfs",
+            Algorithm::Dfs => "dfs",
+            Algorithm::BidirectionalBfs => "bidirectional_bfs",
+            Algorithm::AStar => "a_star",
+            Algorithm::Dijkstra => "dijkstra",
+            Algorithm::WallFollower => "wall_follower",
+            Algorithm::KeyCollector => "key_collector",
         }
     }
-    
-    None
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+pub fn find_path(map: &Map) -> Option<Vec<Position>> {
+    Bfs.solve(map)
+}
+
+fn reconstruct_path(
+    parent: &HashMap<Position, Position>,
+    from: Position,
+    to: Position,
+) -> Vec<Position> {
+    let mut path = vec![to];
+    let mut pos = to;
+    while pos != from {
+        pos = parent[&pos];
+        path.push(pos);
+    }
+    path.reverse();
+    path
 }
@@ -0,0 +1,58 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{reconstruct_path, trace::Observer, SearchResult, Solver};
+use crate::domain::map::{Map, Position};
+
+pub struct Bfs;
+
+impl Solver for Bfs {
+    fn search(&self, map: &Map) -> SearchResult {
+        self.observe(map, &mut ())
+    }
+}
+
+impl Bfs {
+    pub(super) fn observe<O: Observer>(&self, map: &Map, observer: &mut O) -> SearchResult {
+        let mut queue = VecDeque::new();
+        let mut visited = HashSet::new();
+        let mut parent = HashMap::new();
+        let mut explored = 0;
+
+        queue.push_back(map.start);
+        visited.insert(map.start);
+
+        while let Some(current) = queue.pop_front() {
+            explored += 1;
+            if current == map.end {
+                let _ = observer.expanded(current, |limit| frontier(&queue, limit));
+                return SearchResult {
+                    path: Some(reconstruct_path(&parent, map.start, current)),
+                    explored,
+                };
+            }
+
+            for neighbor in map.get_neighbors(current) {
+                if visited.insert(neighbor) {
+                    parent.insert(neighbor, current);
+                    queue.push_back(neighbor);
+                    observer.reached(neighbor);
+                }
+            }
+            if observer
+                .expanded(current, |limit| frontier(&queue, limit))
+                .is_break()
+            {
+                break;
+            }
+        }
+
+        SearchResult {
+            path: None,
+            explored,
+        }
+    }
+}
+
+fn frontier(queue: &VecDeque<Position>, limit: usize) -> Vec<Position> {
+    queue.iter().take(limit).copied().collect()
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use super::{reconstruct_path, SearchResult, Solver};
+use crate::domain::map::{Map, Position};
+
+pub struct BidirectionalBfs;
+
+impl Solver for BidirectionalBfs {
+    fn search(&self, map: &Map) -> SearchResult {
+        if map.start == map.end {
+            return SearchResult {
+                path: Some(vec![map.start]),
+                explored: 1,
+            };
+        }
+
+        // Walking backwards needs the cells that lead *into* a cell, which
+        // only match the neighbors when every move can be undone. Teleporters
+        // are one-way for this purpose: the cell next to the exit did not
+        // lead into it.
+        let reversible = map.moves.is_reversible(map.topology) && map.portals.is_empty();
+        let predecessors = (!reversible).then(|| predecessors(map));
+        let successors = |pos: Position| map.get_neighbors(pos);
+        let backwards = |pos: Position| match &predecessors {
+            Some(predecessors) => predecessors.get(&pos).cloned().unwrap_or_default(),
+            None => map.get_neighbors(pos),
+        };
+
+        let mut forward = Side::new(map.start);
+        let mut backward = Side::new(map.end);
+
+        while !forward.frontier.is_empty() && !backward.frontier.is_empty() {
+            let meeting = if forward.frontier.len() <= backward.frontier.len() {
+                forward.expand(successors, &backward)
+            } else {
+                backward.expand(backwards, &forward)
+            };
+
+            if let Some(meeting) = meeting {
+                let mut path = reconstruct_path(&forward.parent, map.start, meeting);
+                let mut tail = reconstruct_path(&backward.parent, map.end, meeting);
+                tail.pop();
+                path.extend(tail.into_iter().rev());
+                return SearchResult {
+                    path: Some(path),
+                    explored: forward.explored + backward.explored,
+                };
+            }
+        }
+
+        SearchResult {
+            path: None,
+            explored: forward.explored + backward.explored,
+        }
+    }
+}
+
+fn predecessors(map: &Map) -> HashMap<Position, Vec<Position>> {
+    let mut predecessors: HashMap<Position, Vec<Position>> = HashMap::new();
+    for row in 0..map.rows {
+        for col in 0..map.cols {
+            let pos = Position { row, col };
+            if map.cost(pos).is_none() {
+                continue;
+            }
+            for neighbor in map.get_neighbors(pos) {
+                predecessors.entry(neighbor).or_default().push(pos);
+            }
+        }
+    }
+    predecessors
+}
+
+struct Side {
+    frontier: Vec<Position>,
+    depth: HashMap<Position, usize>,
+    parent: HashMap<Position, Position>,
+    explored: usize,
+}
+
+impl Side {
+    fn new(origin: Position) -> Self {
+        Self {
+            frontier: vec![origin],
+            depth: HashMap::from([(origin, 0)]),
+            parent: HashMap::new(),
+            explored: 0,
+        }
+    }
+
+    // Expands a whole level so that the cheapest meeting point is picked
+    // rather than the first one discovered.
+    fn expand<F>(&mut self, neighbors: F, other: &Side) -> Option<Position>
+    where
+        F: Fn(Position) -> Vec<Position>,
+    {
+        let mut next = Vec::new();
+        let mut best: Option<(usize, Position)> = None;
+
+        for current in std::mem::take(&mut self.frontier) {
+            self.explored += 1;
+            let depth = self.depth[&current] + 1;
+            for neighbor in neighbors(current) {
+                if self.depth.contains_key(&neighbor) {
+                    continue;
+                }
+                self.depth.insert(neighbor, depth);
+                self.parent.insert(neighbor, current);
+                next.push(neighbor);
+
+                if let Some(&other_depth) = other.depth.get(&neighbor) {
+                    let total = depth + other_depth;
+                    if best.is_none_or(|(known, _)| total < known) {
+                        best = Some((total, neighbor));
+                    }
+                }
+            }
+        }
+
+        self.frontier = next;
+        best.map(|(_, pos)| pos)
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{reconstruct_path, trace::Observer, SearchResult, Solver};
+use crate::domain::map::{Map, Position};
+
+pub struct Dfs;
+
+impl Solver for Dfs {
+    fn search(&self, map: &Map) -> SearchResult {
+        self.observe(map, &mut ())
+    }
+}
+
+impl Dfs {
+    pub(super) fn observe<O: Observer>(&self, map: &Map, observer: &mut O) -> SearchResult {
+        let mut stack = vec![map.start];
+        let mut visited = HashSet::new();
+        let mut parent = HashMap::new();
+        let mut explored = 0;
+
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            explored += 1;
+            if current == map.end {
+                let _ = observer.expanded(current, |limit| frontier(&stack, &visited, limit));
+                return SearchResult {
+                    path: Some(reconstruct_path(&parent, map.start, current)),
+                    explored,
+                };
+            }
+
+            for neighbor in map.get_neighbors(current) {
+                if !visited.contains(&neighbor) {
+                    parent.insert(neighbor, current);
+                    stack.push(neighbor);
+                    observer.reached(neighbor);
+                }
+            }
+            if observer
+                .expanded(current, |limit| frontier(&stack, &visited, limit))
+                .is_break()
+            {
+                break;
+            }
+        }
+
+        SearchResult {
+            path: None,
+            explored,
+        }
+    }
+}
+
+// The stack holds stale and repeated entries; only the top copy of a cell
+// not yet visited will be expanded.
+fn frontier(stack: &[Position], visited: &HashSet<Position>, limit: usize) -> Vec<Position> {
+    let mut waiting = HashSet::new();
+    stack
+        .iter()
+        .rev()
+        .filter(|pos| !visited.contains(pos) && waiting.insert(**pos))
+        .take(limit)
+        .copied()
+        .collect()
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::domain::map::{Map, Position};
+
+// Fewest steps from one cell to every other cell of the maze.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DistanceField {
+    pub from: Position,
+    // One row per maze row; `None` for walls and cells that cannot be reached.
+    pub distances: Vec<Vec<Option<u32>>>,
+}
+
+impl DistanceField {
+    pub fn compute(map: &Map, from: Position) -> Self {
+        let mut distances = vec![vec![None; map.cols]; map.rows];
+        distances[from.row][from.col] = Some(0);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(current) = queue.pop_front() {
+            let next = distances[current.row][current.col].map(|d| d + 1);
+            for neighbor in map.get_neighbors(current) {
+                let slot = &mut distances[neighbor.row][neighbor.col];
+                if slot.is_none() {
+                    *slot = next;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        Self { from, distances }
+    }
+
+    pub fn get(&self, pos: Position) -> Option<u32> {
+        self.distances
+            .get(pos.row)
+            .and_then(|row| row.get(pos.col))
+            .copied()
+            .flatten()
+    }
+
+    // The reachable cell furthest away, the first one in reading order on ties.
+    pub fn farthest(&self) -> Option<(Position, u32)> {
+        let mut farthest: Option<(Position, u32)> = None;
+        for (row, cells) in self.distances.iter().enumerate() {
+            for (col, distance) in cells.iter().enumerate() {
+                if let Some(distance) = *distance {
+                    if farthest.is_none_or(|(_, best)| distance > best) {
+                        farthest = Some((Position { row, col }, distance));
+                    }
+                }
+            }
+        }
+        farthest
+    }
+}
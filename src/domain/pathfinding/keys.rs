@@ -0,0 +1,132 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{SearchResult, Solver};
+use crate::domain::map::{Cell, Map, Position};
+
+// Breadth-first search over (position, keys held) states, so a door only
+// opens once the matching key has been picked up somewhere earlier.
+pub struct KeyCollector;
+
+type State = (Position, u32);
+
+// Most states a search may have to go through, each open cell once for
+// every set of keys that could be held there.
+pub const MAX_KEY_STATES: usize = 1 << 20;
+
+impl Solver for KeyCollector {
+    fn search(&self, map: &Map) -> SearchResult {
+        let (path, explored) = search_states(map);
+        SearchResult { path, explored }
+    }
+}
+
+// Finds a shortest path through doors along with the keys in the order
+// they were first picked up on the way.
+pub fn solve_with_keys(map: &Map) -> Option<(Vec<Position>, Vec<char>)> {
+    let path = search_states(map).0?;
+    let keys = collected_keys(map, &path);
+    Some((path, keys))
+}
+
+pub fn collected_keys(map: &Map, path: &[Position]) -> Vec<char> {
+    let mut keys = Vec::new();
+    for pos in path {
+        if let Cell::Key(key) = map.grid[pos.row][pos.col] {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+    keys
+}
+
+// Whether the search fits in `MAX_KEY_STATES`, so callers can refuse before
+// starting.
+pub fn check_keys(map: &Map) -> Result<(), String> {
+    let mut open = 0usize;
+    let mut keys = 0u32;
+    for cell in map.grid.iter().flatten() {
+        match cell {
+            Cell::Wall => continue,
+            Cell::Key(key) => keys |= key_bit(*key),
+            _ => {}
+        }
+        open += 1;
+    }
+
+    let key_sets = 1usize.checked_shl(keys.count_ones()).unwrap_or(usize::MAX);
+    if open.saturating_mul(key_sets) > MAX_KEY_STATES {
+        return Err(format!(
+            "maze is too large to search for keys: {} open cells with {} kinds of key, \
+             at most {} states allowed",
+            open,
+            keys.count_ones(),
+            MAX_KEY_STATES
+        ));
+    }
+    Ok(())
+}
+
+fn key_bit(ch: char) -> u32 {
+    1 << (ch.to_ascii_lowercase() as u32 - 'a' as u32)
+}
+
+fn pick_up(map: &Map, pos: Position, held: u32) -> u32 {
+    match map.grid[pos.row][pos.col] {
+        Cell::Key(key) => held | key_bit(key),
+        _ => held,
+    }
+}
+
+fn search_states(map: &Map) -> (Option<Vec<Position>>, usize) {
+    // Callers are expected to check first and report the error; this only
+    // keeps one that did not from hanging.
+    if check_keys(map).is_err() {
+        return (None, 0);
+    }
+    let start = (map.start, pick_up(map, map.start, 0));
+    let mut queue = VecDeque::new();
+    let mut visited = HashSet::new();
+    let mut parent: HashMap<State, State> = HashMap::new();
+    let mut explored = 0;
+
+    queue.push_back(start);
+    visited.insert(start);
+
+    while let Some(current) = queue.pop_front() {
+        explored += 1;
+        let (pos, held) = current;
+        if pos == map.end {
+            return (Some(reconstruct_states(&parent, start, current)), explored);
+        }
+
+        let passable = |cell: &Cell| match cell {
+            Cell::Wall => false,
+            Cell::Door(door) => held & key_bit(*door) != 0,
+            _ => true,
+        };
+        for &offset in map.moves.offsets(pos.row) {
+            let Some(next) = map.step_where(pos, offset, &passable) else {
+                continue;
+            };
+            let state = (next, pick_up(map, next, held));
+            if visited.insert(state) {
+                parent.insert(state, current);
+                queue.push_back(state);
+            }
+        }
+    }
+
+    (None, explored)
+}
+
+fn reconstruct_states(parent: &HashMap<State, State>, from: State, to: State) -> Vec<Position> {
+    let mut path = vec![to.0];
+    let mut state = to;
+    while state != from {
+        state = parent[&state];
+        path.push(state.0);
+    }
+    path.reverse();
+    path
+}
@@ -0,0 +1,127 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::reconstruct_path;
+use crate::domain::map::{Map, Position};
+
+// Fewest-steps path from the start to whichever goal is closest.
+pub fn find_nearest_goal(map: &Map, goals: &[Position]) -> Option<Vec<Position>> {
+    let goals: HashSet<Position> = goals.iter().copied().collect();
+    let mut queue = VecDeque::from([map.start]);
+    let mut visited = HashSet::from([map.start]);
+    let mut parent = HashMap::new();
+
+    while let Some(current) = queue.pop_front() {
+        if goals.contains(&current) {
+            return Some(reconstruct_path(&parent, map.start, current));
+        }
+
+        for neighbor in map.get_neighbors(current) {
+            if visited.insert(neighbor) {
+                parent.insert(neighbor, current);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    None
+}
+
+// Number of distinct fewest-steps paths from start to end, saturating at
+// `u64::MAX`. Zero when the end cannot be reached.
+pub fn count_shortest_paths(map: &Map) -> u64 {
+    let Some(dag) = ShortestPathDag::build(map) else {
+        return 0;
+    };
+
+    let mut counts: HashMap<Position, u64> = HashMap::from([(map.start, 1)]);
+    for &pos in dag.order.iter().skip(1) {
+        let count = dag.predecessors[&pos]
+            .iter()
+            .fold(0u64, |total, p| total.saturating_add(counts[p]));
+        counts.insert(pos, count);
+        if pos == map.end {
+            break;
+        }
+    }
+
+    counts[&map.end]
+}
+
+// Up to `limit` distinct fewest-steps paths from start to end.
+pub fn enumerate_shortest_paths(map: &Map, limit: usize) -> Vec<Vec<Position>> {
+    let mut paths = Vec::new();
+    if limit == 0 {
+        return paths;
+    }
+    let Some(dag) = ShortestPathDag::build(map) else {
+        return paths;
+    };
+
+    // Walk backwards from the end; every predecessor chain in the DAG is a
+    // shortest path, so no dead ends are ever explored.
+    let mut stack = vec![vec![map.end]];
+    while let Some(partial) = stack.pop() {
+        let head = partial[partial.len() - 1];
+        if head == map.start {
+            paths.push(partial.into_iter().rev().collect());
+            if paths.len() == limit {
+                break;
+            }
+            continue;
+        }
+
+        for &predecessor in dag.predecessors[&head].iter().rev() {
+            let mut next = partial.clone();
+            next.push(predecessor);
+            stack.push(next);
+        }
+    }
+
+    paths
+}
+
+// Breadth-first layers from the start, keeping for every cell all the cells
+// one layer closer that lead into it.
+struct ShortestPathDag {
+    order: Vec<Position>,
+    predecessors: HashMap<Position, Vec<Position>>,
+}
+
+impl ShortestPathDag {
+    fn build(map: &Map) -> Option<Self> {
+        let mut depth = HashMap::from([(map.start, 0usize)]);
+        let mut predecessors: HashMap<Position, Vec<Position>> = HashMap::new();
+        let mut order = Vec::new();
+        let mut queue = VecDeque::from([map.start]);
+
+        while let Some(current) = queue.pop_front() {
+            order.push(current);
+            let next_depth = depth[&current] + 1;
+            if depth.get(&map.end).is_some_and(|&end| next_depth > end) {
+                continue;
+            }
+
+            let mut neighbors = map.get_neighbors(current);
+            neighbors.sort();
+            neighbors.dedup();
+            for neighbor in neighbors {
+                match depth.get(&neighbor) {
+                    None => {
+                        depth.insert(neighbor, next_depth);
+                        predecessors.entry(neighbor).or_default().push(current);
+                        queue.push_back(neighbor);
+                    }
+                    Some(&known) if known == next_depth => {
+                        predecessors.entry(neighbor).or_default().push(current);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        depth.contains_key(&map.end).then_some(Self {
+            order,
+            predecessors,
+        })
+    }
+}
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use super::{weighted, Algorithm, Bfs, Dfs, SearchResult};
+use crate::domain::map::{Map, Position};
+
+// Cells a trace frame lists from the frontier, so a frame costs the same
+// however large the frontier grows.
+pub const MAX_FRONTIER: usize = 32;
+
+// Tracing sends a frame per expanded cell, so larger mazes are refused.
+pub const MAX_TRACE_CELLS: usize = 10_000;
+
+// Hooks a solver calls while it runs. The plain solvers pass `()`, which
+// ignores everything, so only traced searches pay for the frontier copies.
+pub(super) trait Observer {
+    // A cell was put on the frontier, or put back with a better parent.
+    fn reached(&mut self, _pos: Position) {}
+
+    // A cell was taken off the frontier and its neighbors looked at.
+    // `frontier(limit)` lists up to `limit` cells still waiting, next to be
+    // expanded first. `Break` stops the search.
+    fn expanded(
+        &mut self,
+        _current: Position,
+        _frontier: impl FnOnce(usize) -> Vec<Position>,
+    ) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+impl Observer for () {}
+
+// One expansion of a traced search.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceFrame {
+    // Counts from 1; the last frame's step equals the search's `explored`.
+    pub step: usize,
+    pub current: Position,
+    // Cells this expansion added to the frontier.
+    pub discovered: Vec<Position>,
+    // The first `MAX_FRONTIER` cells waiting.
+    pub frontier: Vec<Position>,
+    // Distinct cells reached so far, expanded or waiting.
+    pub seen: usize,
+}
+
+struct Recorder<F> {
+    step: usize,
+    discovered: Vec<Position>,
+    seen: HashSet<Position>,
+    stopped: bool,
+    emit: F,
+}
+
+impl<F: FnMut(TraceFrame) -> ControlFlow<()>> Observer for Recorder<F> {
+    fn reached(&mut self, pos: Position) {
+        self.discovered.push(pos);
+        self.seen.insert(pos);
+    }
+
+    fn expanded(
+        &mut self,
+        current: Position,
+        frontier: impl FnOnce(usize) -> Vec<Position>,
+    ) -> ControlFlow<()> {
+        self.step += 1;
+        let flow = (self.emit)(TraceFrame {
+            step: self.step,
+            current,
+            discovered: std::mem::take(&mut self.discovered),
+            frontier: frontier(MAX_FRONTIER),
+            seen: self.seen.len(),
+        });
+        self.stopped = flow.is_break();
+        flow
+    }
+}
+
+pub fn can_trace(algorithm: Algorithm) -> bool {
+    matches!(
+        algorithm,
+        Algorithm::Bfs | Algorithm::Dfs | Algorithm::AStar | Algorithm::Dijkstra
+    )
+}
+
+// Whether `trace` would run, so callers can refuse before starting.
+pub fn check_trace(map: &Map, algorithm: Algorithm) -> Result<(), String> {
+    if !can_trace(algorithm) {
+        return Err(format!("tracing is not supported for {}", algorithm));
+    }
+    if map.rows * map.cols > MAX_TRACE_CELLS {
+        return Err(format!(
+            "maze is too large to trace, at most {} cells allowed",
+            MAX_TRACE_CELLS
+        ));
+    }
+    Ok(())
+}
+
+// Runs the same search as `algorithm.solver()`, handing every expansion
+// to `emit` as it happens. `None` when `emit` stopped the search early.
+pub fn trace<F>(map: &Map, algorithm: Algorithm, emit: F) -> Result<Option<SearchResult>, String>
+where
+    F: FnMut(TraceFrame) -> ControlFlow<()>,
+{
+    check_trace(map, algorithm)?;
+    let mut recorder = Recorder {
+        step: 0,
+        discovered: Vec::new(),
+        seen: HashSet::from([map.start]),
+        stopped: false,
+        emit,
+    };
+    let result = match algorithm {
+        Algorithm::Bfs => Bfs.observe(map, &mut recorder),
+        Algorithm::Dfs => Dfs.observe(map, &mut recorder),
+        Algorithm::AStar => weighted::observe(map, weighted::heuristic(map), &mut recorder),
+        Algorithm::Dijkstra => weighted::observe(map, |_| 0, &mut recorder),
+        _ => unreachable!("checked above"),
+    };
+    Ok((!recorder.stopped).then_some(result))
+}
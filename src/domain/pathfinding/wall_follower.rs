@@ -0,0 +1,80 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+
+use super::{SearchResult, Solver};
+use crate::domain::map::Map;
+
+pub struct WallFollower;
+
+// Keeps the left hand on the wall: at every cell the walker takes the move
+// that turns furthest to the left of its current heading, and only turns
+// back the way it came as a last resort. With move sets other than the
+// orthogonal one "left" is decided by the angle of each move.
+impl Solver for WallFollower {
+    fn search(&self, map: &Map) -> SearchResult {
+        let mut route = vec![map.start];
+        let mut index = HashMap::from([(map.start, 0)]);
+        let mut seen = HashSet::new();
+        let mut pos = map.start;
+        let mut heading = (-1, 0);
+
+        while pos != map.end {
+            // Coming back to the same cell facing the same way means the
+            // wall we are following never reaches the exit.
+            if !seen.insert((pos, heading)) {
+                return SearchResult {
+                    path: None,
+                    explored: seen.len(),
+                };
+            }
+
+            let mut offsets = map.moves.offsets(pos.row).to_vec();
+            offsets.sort_by(|&a, &b| {
+                turn(heading, b)
+                    .partial_cmp(&turn(heading, a))
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            let Some((next, offset)) = offsets
+                .into_iter()
+                .find_map(|offset| map.step(pos, offset).map(|next| (next, offset)))
+            else {
+                return SearchResult {
+                    path: None,
+                    explored: seen.len(),
+                };
+            };
+
+            // Erase loops so the returned route never visits a cell twice.
+            if let Some(&earlier) = index.get(&next) {
+                for dropped in route.drain(earlier + 1..) {
+                    index.remove(&dropped);
+                }
+            } else {
+                index.insert(next, route.len());
+                route.push(next);
+            }
+
+            pos = next;
+            heading = offset;
+        }
+
+        SearchResult {
+            path: Some(route),
+            explored: seen.len() + 1,
+        }
+    }
+}
+
+// Counter-clockwise angle from `heading` to `offset` in [-PI, PI), with a
+// full reversal mapped to -PI so that it sorts after every right turn.
+fn turn(heading: (i32, i32), offset: (i32, i32)) -> f64 {
+    let angle = |(dr, dc): (i32, i32)| (-dr as f64).atan2(dc as f64);
+    let turn = (angle(offset) - angle(heading)).rem_euclid(2.0 * PI);
+    if turn >= PI - 1e-9 {
+        turn - 2.0 * PI
+    } else {
+        turn
+    }
+}
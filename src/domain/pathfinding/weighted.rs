@@ -0,0 +1,139 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use super::{reconstruct_path, trace::Observer, SearchResult, Solver};
+use crate::domain::map::{Cell, Map, Position};
+
+pub struct Dijkstra;
+
+pub struct AStar;
+
+impl Solver for Dijkstra {
+    fn search(&self, map: &Map) -> SearchResult {
+        observe(map, |_| 0, &mut ())
+    }
+}
+
+impl Solver for AStar {
+    fn search(&self, map: &Map) -> SearchResult {
+        observe(map, heuristic(map), &mut ())
+    }
+}
+
+pub fn dijkstra(map: &Map) -> Option<(Vec<Position>, u32)> {
+    cheapest_path(map, |_| 0, &mut ()).0
+}
+
+pub fn a_star(map: &Map) -> Option<(Vec<Position>, u32)> {
+    cheapest_path(map, heuristic(map), &mut ()).0
+}
+
+pub(super) fn observe<H, O>(map: &Map, heuristic: H, observer: &mut O) -> SearchResult
+where
+    H: Fn(Position) -> u32,
+    O: Observer,
+{
+    let (found, explored) = cheapest_path(map, heuristic, observer);
+    SearchResult {
+        path: found.map(|(path, _)| path),
+        explored,
+    }
+}
+
+pub(super) fn heuristic(map: &Map) -> impl Fn(Position) -> u32 + '_ {
+    let min_cost = Cell::Road.cost().unwrap_or(1);
+    // A teleporter can shortcut any distance, so no estimate is safe.
+    let jumps = !map.portals.is_empty();
+    move |pos| {
+        if jumps {
+            return 0;
+        }
+        let moves = map
+            .topology
+            .displacements(pos, map.end, map.rows, map.cols)
+            .into_iter()
+            .map(|(rows, cols)| map.moves.min_moves(rows, cols))
+            .min()
+            .unwrap_or(0);
+        moves as u32 * min_cost
+    }
+}
+
+// Returns the cheapest path with its cost, and the number of expanded cells.
+fn cheapest_path<H, O>(
+    map: &Map,
+    heuristic: H,
+    observer: &mut O,
+) -> (Option<(Vec<Position>, u32)>, usize)
+where
+    H: Fn(Position) -> u32,
+    O: Observer,
+{
+    let mut heap = BinaryHeap::new();
+    let mut best = HashMap::new();
+    let mut parent = HashMap::new();
+    let mut explored = 0;
+
+    best.insert(map.start, 0);
+    heap.push(Reverse((heuristic(map.start), 0, map.start)));
+
+    while let Some(Reverse((_, cost, current))) = heap.pop() {
+        if cost > best[&current] {
+            continue;
+        }
+
+        explored += 1;
+        if current == map.end {
+            let _ = observer.expanded(current, |limit| frontier(&heap, &best, limit));
+            let path = reconstruct_path(&parent, map.start, current);
+            return (Some((path, cost)), explored);
+        }
+
+        for neighbor in map.get_neighbors(current) {
+            let Some(step) = map.cost(neighbor) else {
+                continue;
+            };
+            let next_cost = cost + step;
+            if best.get(&neighbor).is_none_or(|&known| next_cost < known) {
+                best.insert(neighbor, next_cost);
+                parent.insert(neighbor, current);
+                heap.push(Reverse((
+                    next_cost + heuristic(neighbor),
+                    next_cost,
+                    neighbor,
+                )));
+                observer.reached(neighbor);
+            }
+        }
+        if observer
+            .expanded(current, |limit| frontier(&heap, &best, limit))
+            .is_break()
+        {
+            break;
+        }
+    }
+
+    (None, explored)
+}
+
+type Entry = Reverse<(u32, u32, Position)>;
+
+// The first `limit` live heap entries in the order they will be popped;
+// entries for cells that were since reached more cheaply are skipped. Only
+// those are sorted, not the whole heap.
+fn frontier(
+    heap: &BinaryHeap<Entry>,
+    best: &HashMap<Position, u32>,
+    limit: usize,
+) -> Vec<Position> {
+    let mut entries: Vec<&Entry> = heap
+        .iter()
+        .filter(|Reverse((_, cost, pos))| *cost == best[pos])
+        .collect();
+    if entries.len() > limit && limit > 0 {
+        entries.select_nth_unstable_by(limit - 1, |a, b| b.cmp(a));
+    }
+    entries.truncate(limit);
+    entries.sort_by(|a, b| b.cmp(a));
+    entries.iter().map(|Reverse((_, _, pos))| *pos).collect()
+}
@@ -1,5 +1,8 @@
 use super::pathfinding::find_path;
 use super::map::{Map, Position, Cell};
+use super::moves::MoveSet;
+use super::topology::Topology;
+use std::collections::HashMap;
 
 #[test]
 fn test_path_finding() {
@@ -13,6 +16,9 @@ fn test_path_finding() {
         end: Position { row: 2, col: 2 },
         rows: 3,
         cols: 3,
+        topology: Topology::Torus,
+        moves: MoveSet::Orthogonal,
+        portals: HashMap::new(),
     };
 
     let path = find_path(&map);
@@ -36,6 +42,9 @@ fn test_no_path() {
         end: Position { row: 1, col: 1 },
         rows: 3,
         cols: 3,
+        topology: Topology::Torus,
+        moves: MoveSet::Orthogonal,
+        portals: HashMap::new(),
     };
 
     let path = find_path(&map);
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::domain::map::{Cell, Map, Position};
+use crate::domain::pathfinding::find_path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn offset(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+// A player walking through a maze by hand, one step at a time. Steps
+// follow the same rules as the solvers, wrapping and teleporters included.
+pub struct Game {
+    map: Map,
+    // Every cell the player stood on, starting with the start.
+    route: Vec<Position>,
+    // How far the player can see, in cells; `None` shows the whole maze.
+    fog: Option<usize>,
+    seen: HashSet<Position>,
+}
+
+impl Game {
+    pub fn new(map: Map, fog: Option<usize>) -> Self {
+        let mut game = Self {
+            route: vec![map.start],
+            map,
+            fog,
+            seen: HashSet::new(),
+        };
+        game.look_around();
+        game
+    }
+
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    pub fn player(&self) -> Position {
+        self.route[self.route.len() - 1]
+    }
+
+    pub fn moves(&self) -> usize {
+        self.route.len() - 1
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.player() == self.map.end
+    }
+
+    // Returns whether the player moved. Steps the current move set does not
+    // allow, such as any arrow key with knight moves, are refused.
+    pub fn step(&mut self, direction: Direction) -> bool {
+        if self.is_won() {
+            return false;
+        }
+        let player = self.player();
+        let Some(target) = self.map.step(player, direction.offset()) else {
+            return false;
+        };
+        if !self.map.get_neighbors(player).contains(&target) {
+            return false;
+        }
+
+        self.route.push(target);
+        self.look_around();
+        true
+    }
+
+    pub fn is_visible(&self, pos: Position) -> bool {
+        let Some(radius) = self.fog else {
+            return true;
+        };
+        self.map
+            .topology
+            .displacements(self.player(), pos, self.map.rows, self.map.cols)
+            .iter()
+            .any(|&(dr, dc)| dr.max(dc) <= radius)
+    }
+
+    fn look_around(&mut self) {
+        if self.fog.is_none() {
+            return;
+        }
+        for row in 0..self.map.rows {
+            for col in 0..self.map.cols {
+                let pos = Position { row, col };
+                if self.is_visible(pos) {
+                    self.seen.insert(pos);
+                }
+            }
+        }
+    }
+
+    // The maze as the player sees it, one string per row: `@` for the
+    // player, `.` for cells already walked and `░` for cells never seen.
+    // Cells seen before but out of sight now are drawn from memory.
+    pub fn view(&self) -> Vec<String> {
+        let player = self.player();
+        let walked: HashSet<Position> = self.route.iter().copied().collect();
+
+        (0..self.map.rows)
+            .map(|row| {
+                (0..self.map.cols)
+                    .map(|col| {
+                        let pos = Position { row, col };
+                        if pos == player {
+                            '@'
+                        } else if self.fog.is_some() && !self.seen.contains(&pos) {
+                            '░'
+                        } else if pos == self.map.end {
+                            'O'
+                        } else if walked.contains(&pos) {
+                            '.'
+                        } else {
+                            symbol(self.map.grid[row][col])
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn report(&self) -> Report {
+        Report {
+            finished: self.is_won(),
+            moves: self.moves(),
+            optimal: find_path(&self.map).map(|path| path.len() - 1),
+        }
+    }
+}
+
+fn symbol(cell: Cell) -> char {
+    match cell {
+        Cell::Wall => '#',
+        Cell::Road => '=',
+        Cell::Mud => '%',
+        Cell::Water => '~',
+        Cell::Key(ch) | Cell::Door(ch) | Cell::Teleporter(ch) => ch,
+        Cell::Empty | Cell::Start | Cell::End | Cell::Path => ' ',
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub finished: bool,
+    pub moves: usize,
+    // Steps on a shortest route, `None` when the end cannot be reached.
+    pub optimal: Option<usize>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.finished {
+            return write!(f, "Gave up after {} moves.", self.moves);
+        }
+        write!(f, "Reached the end in {} moves", self.moves)?;
+        match self.optimal {
+            Some(optimal) if optimal == self.moves => write!(f, ", a shortest route."),
+            Some(optimal) => write!(
+                f,
+                "; the shortest route takes {} ({} extra, {}% efficient).",
+                optimal,
+                self.moves.saturating_sub(optimal),
+                optimal * 100 / self.moves
+            ),
+            None => write!(f, "."),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+mod png;
+mod svg;
+mod text;
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::domain::map::{Cell, Map, Position};
+
+pub use self::png::render_png;
+pub use self::svg::render_svg;
+pub use self::text::{render_text, TextStyle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderFormat {
+    Png,
+    Svg,
+    Ascii,
+    Unicode,
+    Ansi,
+}
+
+impl RenderFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RenderFormat::Png => "png",
+            RenderFormat::Svg => "svg",
+            RenderFormat::Ascii => "ascii",
+            RenderFormat::Unicode => "unicode",
+            RenderFormat::Ansi => "ansi",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            RenderFormat::Png => "image/png",
+            RenderFormat::Svg => "image/svg+xml",
+            RenderFormat::Ascii | RenderFormat::Unicode | RenderFormat::Ansi => {
+                "text/plain; charset=utf-8"
+            }
+        }
+    }
+
+    // Only the image formats are picked by the Accept header; text is
+    // always asked for by name.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type {
+            "image/png" => Some(RenderFormat::Png),
+            "image/svg+xml" => Some(RenderFormat::Svg),
+            _ => None,
+        }
+    }
+
+    pub fn is_image(&self) -> bool {
+        matches!(self, RenderFormat::Png | RenderFormat::Svg)
+    }
+}
+
+impl fmt::Display for RenderFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RenderFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "png" => Ok(RenderFormat::Png),
+            "svg" => Ok(RenderFormat::Svg),
+            "ascii" => Ok(RenderFormat::Ascii),
+            "unicode" => Ok(RenderFormat::Unicode),
+            "ansi" => Ok(RenderFormat::Ansi),
+            _ => Err(format!("unknown format '{}'", s)),
+        }
+    }
+}
+
+// What is drawn on top of the maze in the text formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overlay {
+    // The path as dots.
+    #[default]
+    Path,
+    // The path as step numbers, modulo 10.
+    Steps,
+    // Every reachable cell shaded by its distance from the start, 0 to 9.
+    Heatmap,
+}
+
+impl FromStr for Overlay {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "path" => Ok(Overlay::Path),
+            "steps" => Ok(Overlay::Steps),
+            "heatmap" => Ok(Overlay::Heatmap),
+            _ => Err(format!("unknown overlay '{}'", s)),
+        }
+    }
+}
+
+// An RGB colour, written as `#rrggbb`. The `#` is optional when parsing so
+// colours can be passed in a query string without escaping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    // The colour `t` of the way from `self` to `other`, `t` in `0.0..=1.0`.
+    pub fn lerp(self, other: Color, t: f64) -> Color {
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Color(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("invalid color '{}', expected #rrggbb", s));
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap();
+        Ok(Color(channel(0), channel(2), channel(4)))
+    }
+}
+
+impl TryFrom<String> for Color {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Color> for String {
+    fn from(color: Color) -> Self {
+        color.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderOptions {
+    // Side of one maze cell in pixels.
+    pub cell_size: u32,
+    pub wall: Color,
+    pub floor: Color,
+    pub start: Color,
+    pub end: Color,
+    // The path fades from `path_start` at its first step to `path_end` at
+    // its last, so the order of the steps can be read off the picture.
+    pub path_start: Color,
+    pub path_end: Color,
+    pub overlay: Overlay,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 16,
+            wall: Color(0x22, 0x22, 0x22),
+            floor: Color(0xff, 0xff, 0xff),
+            start: Color(0x2e, 0x7d, 0x32),
+            end: Color(0xc6, 0x28, 0x28),
+            path_start: Color(0xff, 0xd5, 0x4f),
+            path_end: Color(0x15, 0x65, 0xc0),
+            overlay: Overlay::default(),
+        }
+    }
+}
+
+// Terrain and the other special cells are tinted with fixed colours.
+fn cell_color(cell: Cell, options: &RenderOptions) -> Color {
+    match cell {
+        Cell::Wall => options.wall,
+        Cell::Road => Color(0xbd, 0xbd, 0xbd),
+        Cell::Mud => Color(0x8d, 0x6e, 0x63),
+        Cell::Water => Color(0x81, 0xd4, 0xfa),
+        Cell::Key(_) => Color(0xff, 0xb3, 0x00),
+        Cell::Door(_) => Color(0x6d, 0x4c, 0x41),
+        Cell::Teleporter(_) => Color(0xab, 0x47, 0xbc),
+        Cell::Empty | Cell::Start | Cell::End | Cell::Path => options.floor,
+    }
+}
+
+// The colour of every cell, row by row. Start and end are taken from the
+// map rather than the grid, so they can be moved without editing it.
+pub fn cell_colors(map: &Map, path: &[Position], options: &RenderOptions) -> Vec<Vec<Color>> {
+    let mut colors: Vec<Vec<Color>> = map
+        .grid
+        .iter()
+        .map(|row| row.iter().map(|&cell| cell_color(cell, options)).collect())
+        .collect();
+
+    let last = path.len().saturating_sub(1).max(1) as f64;
+    for (i, pos) in path.iter().enumerate() {
+        colors[pos.row][pos.col] = options.path_start.lerp(options.path_end, i as f64 / last);
+    }
+    colors[map.start.row][map.start.col] = options.start;
+    colors[map.end.row][map.end.col] = options.end;
+    colors
+}
+
+pub fn render(
+    map: &Map,
+    path: &[Position],
+    format: RenderFormat,
+    options: &RenderOptions,
+) -> Result<Vec<u8>, String> {
+    let text = |style| Ok(render_text(map, path, style, options).into_bytes());
+    match format {
+        RenderFormat::Png => render_png(map, path, options),
+        RenderFormat::Svg => Ok(render_svg(map, path, options).into_bytes()),
+        RenderFormat::Ascii => text(TextStyle::Ascii),
+        RenderFormat::Unicode => text(TextStyle::Unicode),
+        RenderFormat::Ansi => text(TextStyle::Ansi),
+    }
+}
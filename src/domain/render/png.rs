@@ -0,0 +1,37 @@
+use super::{cell_colors, RenderOptions};
+use crate::domain::map::{Map, Position};
+
+pub fn render_png(
+    map: &Map,
+    path: &[Position],
+    options: &RenderOptions,
+) -> Result<Vec<u8>, String> {
+    let size = options.cell_size as usize;
+    let width = map.cols * size;
+    let height = map.rows * size;
+
+    let colors = cell_colors(map, path, options);
+    let mut pixels = Vec::with_capacity(width * height * 3);
+    for row in &colors {
+        let mut line = Vec::with_capacity(width * 3);
+        for color in row {
+            for _ in 0..size {
+                line.extend_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+        for _ in 0..size {
+            pixels.extend_from_slice(&line);
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer
+        .write_image_data(&pixels)
+        .map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(out)
+}
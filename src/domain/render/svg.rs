@@ -0,0 +1,40 @@
+use std::fmt::Write;
+
+use super::{cell_colors, RenderOptions};
+use crate::domain::map::{Map, Position};
+
+pub fn render_svg(map: &Map, path: &[Position], options: &RenderOptions) -> String {
+    let size = options.cell_size as usize;
+    let (width, height) = (map.cols * size, map.rows * size);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\" shape-rendering=\"crispEdges\">\n",
+        w = width,
+        h = height
+    );
+    // Floor cells are left to the background.
+    writeln!(
+        svg,
+        "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        width, height, options.floor
+    )
+    .unwrap();
+    for (row, colors) in cell_colors(map, path, options).iter().enumerate() {
+        for (col, color) in colors.iter().enumerate() {
+            if *color != options.floor {
+                writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\" fill=\"{}\"/>",
+                    col * size,
+                    row * size,
+                    color,
+                    s = size
+                )
+                .unwrap();
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,124 @@
+use super::{Color, Overlay, RenderOptions};
+use crate::domain::map::{Cell, Map, Position};
+use crate::domain::pathfinding::DistanceField;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextStyle {
+    // The same characters the maze is written in.
+    Ascii,
+    // Walls drawn with box-drawing characters.
+    Unicode,
+    // Unicode with start, end and path coloured by ANSI escapes.
+    Ansi,
+}
+
+const RESET: &str = "\x1b[0m";
+
+pub fn render_text(
+    map: &Map,
+    path: &[Position],
+    style: TextStyle,
+    options: &RenderOptions,
+) -> String {
+    // Step index of every cell on the path; a revisited cell keeps its last.
+    let mut steps = vec![vec![None; map.cols]; map.rows];
+    for (i, pos) in path.iter().enumerate() {
+        steps[pos.row][pos.col] = Some(i);
+    }
+    let last_step = path.len().saturating_sub(1).max(1) as f64;
+
+    let heat =
+        (options.overlay == Overlay::Heatmap).then(|| DistanceField::compute(map, map.start));
+    let max_distance = heat
+        .as_ref()
+        .and_then(|field| field.farthest())
+        .map_or(1, |(_, distance)| distance.max(1)) as f64;
+
+    let mut out = String::new();
+    for (row, (cells, row_steps)) in map.grid.iter().zip(&steps).enumerate() {
+        for (col, (&cell, step)) in cells.iter().zip(row_steps).enumerate() {
+            let pos = Position { row, col };
+
+            if cell == Cell::Wall {
+                out.push(match style {
+                    TextStyle::Ascii => '#',
+                    TextStyle::Unicode | TextStyle::Ansi => box_drawing(map, pos),
+                });
+                continue;
+            }
+
+            let (ch, color) = if pos == map.start {
+                ('i', Some(options.start))
+            } else if pos == map.end {
+                ('O', Some(options.end))
+            } else if let Some(distance) = heat.as_ref().and_then(|field| field.get(pos)) {
+                let t = distance as f64 / max_distance;
+                let digit = char::from_digit((t * 9.0).round() as u32, 10).unwrap();
+                (digit, Some(options.path_start.lerp(options.path_end, t)))
+            } else if let Some(step) = step.filter(|_| options.overlay != Overlay::Heatmap) {
+                let color = options
+                    .path_start
+                    .lerp(options.path_end, step as f64 / last_step);
+                let ch = match (options.overlay, style) {
+                    (Overlay::Steps, _) => char::from_digit((step % 10) as u32, 10).unwrap(),
+                    (_, TextStyle::Ascii) => '.',
+                    _ => '·',
+                };
+                (ch, Some(color))
+            } else {
+                (floor_char(cell), None)
+            };
+
+            match (style, color) {
+                (TextStyle::Ansi, Some(Color(r, g, b))) => {
+                    out.push_str(&format!("\x1b[1;38;2;{};{};{}m{}{}", r, g, b, ch, RESET))
+                }
+                _ => out.push(ch),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn floor_char(cell: Cell) -> char {
+    match cell {
+        Cell::Road => '=',
+        Cell::Mud => '%',
+        Cell::Water => '~',
+        Cell::Key(ch) | Cell::Door(ch) | Cell::Teleporter(ch) => ch,
+        // Start and end are drawn where the map puts them, which need not
+        // be where the maze text had them.
+        Cell::Wall | Cell::Empty | Cell::Start | Cell::End | Cell::Path => ' ',
+    }
+}
+
+// Joins a wall to the walls next to it. Edges are not followed across a
+// wrapping topology, so the outline matches the text it came from.
+fn box_drawing(map: &Map, pos: Position) -> char {
+    let wall = |dr: i64, dc: i64| {
+        let (row, col) = (pos.row as i64 + dr, pos.col as i64 + dc);
+        (0..map.rows as i64).contains(&row)
+            && (0..map.cols as i64).contains(&col)
+            && map.grid[row as usize][col as usize] == Cell::Wall
+    };
+
+    match (wall(-1, 0), wall(0, 1), wall(1, 0), wall(0, -1)) {
+        (false, false, false, false) => '▪',
+        (true, false, false, false) => '╵',
+        (false, true, false, false) => '╶',
+        (false, false, true, false) => '╷',
+        (false, false, false, true) => '╴',
+        (true, false, true, false) => '│',
+        (false, true, false, true) => '─',
+        (true, true, false, false) => '└',
+        (false, true, true, false) => '┌',
+        (false, false, true, true) => '┐',
+        (true, false, false, true) => '┘',
+        (true, true, true, false) => '├',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┤',
+        (true, true, false, true) => '┴',
+        (true, true, true, true) => '┼',
+    }
+}
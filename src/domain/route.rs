@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::map::{Cell, Map, Position};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Step {
+    pub from: Position,
+    pub to: Position,
+    // Letters for the move, vertical before horizontal: "U", "DR", "UUL".
+    // Longer jumps give counts instead: "D5R1".
+    pub direction: String,
+    pub dr: i32,
+    pub dc: i32,
+    // The move crossed an edge of the grid and came back on the other side.
+    pub wrapped: bool,
+    // The teleporter the move stepped onto before jumping to `to`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teleport: Option<Position>,
+}
+
+// Describes every move of a path, or `None` if two consecutive cells are
+// not connected by a single move.
+pub fn describe(map: &Map, path: &[Position]) -> Option<Vec<Step>> {
+    path.windows(2)
+        .map(|pair| describe_step(map, pair[0], pair[1]))
+        .collect()
+}
+
+fn describe_step(map: &Map, from: Position, to: Position) -> Option<Step> {
+    let mut candidates: Vec<((i32, i32), bool, Option<Position>)> = map
+        .moves
+        .offsets(from.row)
+        .iter()
+        // Doors are let through here: whether they were open is the
+        // solver's concern, not the route's.
+        .filter(|&&offset| map.step_where(from, offset, &|cell| *cell != Cell::Wall) == Some(to))
+        .map(|&(dr, dc)| {
+            let row = from.row as i64 + dr as i64;
+            let col = from.col as i64 + dc as i64;
+            let wrapped =
+                !(0..map.rows as i64).contains(&row) || !(0..map.cols as i64).contains(&col);
+            let landed = map.topology.wrap(row, col, map.rows, map.cols);
+            let teleport = landed.filter(|&landed| landed != to);
+            ((dr, dc), wrapped, teleport)
+        })
+        .collect();
+
+    // On tiny wrapping grids two moves can land on the same cell; the one
+    // that stays inside the grid is the natural reading. Walking beats
+    // teleporting for the same reason.
+    candidates.sort_by_key(|&(_, wrapped, teleport)| (teleport.is_some(), wrapped));
+    let ((dr, dc), wrapped, teleport) = candidates.into_iter().next()?;
+
+    Some(Step {
+        from,
+        to,
+        direction: direction(dr, dc),
+        dr,
+        dc,
+        wrapped,
+        teleport,
+    })
+}
+
+// Moves up to this many cells along each axis are spelled letter by letter,
+// which covers every built-in move set.
+const MAX_SPELLED: u32 = 2;
+
+fn direction(dr: i32, dc: i32) -> String {
+    let vertical = if dr < 0 { 'U' } else { 'D' };
+    let horizontal = if dc < 0 { 'L' } else { 'R' };
+
+    if dr.unsigned_abs().max(dc.unsigned_abs()) > MAX_SPELLED {
+        return [(vertical, dr), (horizontal, dc)]
+            .into_iter()
+            .filter(|&(_, length)| length != 0)
+            .map(|(letter, length)| format!("{}{}", letter, length.unsigned_abs()))
+            .collect();
+    }
+    std::iter::repeat_n(vertical, dr.unsigned_abs() as usize)
+        .chain(std::iter::repeat_n(horizontal, dc.unsigned_abs() as usize))
+        .collect()
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::domain::map::Position;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topology {
+    // No wrapping, the edges of the grid are solid.
+    Bounded,
+    // Left and right edges are joined (a vertical cylinder).
+    Horizontal,
+    // Top and bottom edges are joined (a horizontal cylinder).
+    Vertical,
+    #[default]
+    Torus,
+    // Left and right edges are joined with the rows flipped upside down.
+    Mobius,
+    // Like `Mobius`, with the top and bottom edges joined as well.
+    Klein,
+}
+
+impl Topology {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Topology::Bounded => "bounded",
+            Topology::Horizontal => "horizontal",
+            Topology::Vertical => "vertical",
+            Topology::Torus => "torus",
+            Topology::Mobius => "mobius",
+            Topology::Klein => "klein",
+        }
+    }
+
+    fn wraps_rows(&self) -> bool {
+        matches!(self, Topology::Vertical | Topology::Torus | Topology::Klein)
+    }
+
+    fn wraps_cols(&self) -> bool {
+        !matches!(self, Topology::Bounded | Topology::Vertical)
+    }
+
+    pub fn flips_rows(&self) -> bool {
+        matches!(self, Topology::Mobius | Topology::Klein)
+    }
+
+    // Maps a possibly out-of-range coordinate back onto the grid, or `None`
+    // when it falls off an edge that does not wrap.
+    pub fn wrap(&self, row: i64, col: i64, rows: usize, cols: usize) -> Option<Position> {
+        let (rows, cols) = (rows as i64, cols as i64);
+
+        let mut row = if self.wraps_rows() {
+            row.rem_euclid(rows)
+        } else if (0..rows).contains(&row) {
+            row
+        } else {
+            return None;
+        };
+
+        let col = if self.wraps_cols() {
+            if self.flips_rows() && col.div_euclid(cols) % 2 != 0 {
+                row = rows - 1 - row;
+            }
+            col.rem_euclid(cols)
+        } else if (0..cols).contains(&col) {
+            col
+        } else {
+            return None;
+        };
+
+        Some(Position {
+            row: row as usize,
+            col: col as usize,
+        })
+    }
+
+    // Row and column distances between two cells, once for the direct
+    // route and once for the route across the column seam when there is one.
+    pub fn displacements(
+        &self,
+        a: Position,
+        b: Position,
+        rows: usize,
+        cols: usize,
+    ) -> Vec<(usize, usize)> {
+        let row_distance = |from: usize, to: usize| {
+            let direct = from.abs_diff(to);
+            if self.wraps_rows() {
+                direct.min(rows - direct)
+            } else {
+                direct
+            }
+        };
+
+        let dc = a.col.abs_diff(b.col);
+        let mut displacements = vec![(row_distance(a.row, b.row), dc)];
+        if self.wraps_cols() {
+            let across_row = if self.flips_rows() {
+                rows - 1 - b.row
+            } else {
+                b.row
+            };
+            displacements.push((row_distance(a.row, across_row), cols - dc));
+        }
+        displacements
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bounded" => Ok(Topology::Bounded),
+            "horizontal" => Ok(Topology::Horizontal),
+            "vertical" => Ok(Topology::Vertical),
+            "torus" => Ok(Topology::Torus),
+            "mobius" => Ok(Topology::Mobius),
+            "klein" => Ok(Topology::Klein),
+            _ => Err(format!("Unknown topology: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::domain::analysis;
+use crate::domain::map::{Cell, Map, Position};
+use crate::domain::pathfinding::{check_keys, KeyCollector, Solver};
+
+// Offending cells or regions listed in one message before the rest are
+// only counted.
+const MAX_REPORTED: usize = 5;
+
+// Rules a maze has to meet before it is stored. Everything is off by
+// default, which only requires the content to parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationPolicy {
+    // The end can be reached from the start, picking up keys for doors.
+    pub require_solvable: bool,
+    // Every open cell can be reached from the start. Doors count as open
+    // here, since a key may open them.
+    pub forbid_isolated_regions: bool,
+    // Exactly one route leads from start to end without visiting a cell
+    // twice. Implies `require_solvable`.
+    pub require_unique_solution: bool,
+    pub max_rows: Option<usize>,
+    pub max_cols: Option<usize>,
+}
+
+// Checks every rule at once and reports all that fail, separated by `; `,
+// each naming the cells at fault.
+pub fn validate(map: &Map, policy: &ValidationPolicy) -> Result<(), String> {
+    let mut problems = Vec::new();
+    if let Some(max) = policy.max_rows.filter(|&max| map.rows > max) {
+        problems.push(format!("{} rows, at most {} allowed", map.rows, max));
+    }
+    if let Some(max) = policy.max_cols.filter(|&max| map.cols > max) {
+        problems.push(format!("{} columns, at most {} allowed", map.cols, max));
+    }
+    // The other rules search the whole maze, which an oversized one should
+    // not get to make us do.
+    if !problems.is_empty() {
+        return Err(problems.join("; "));
+    }
+
+    // Each search only runs when a rule needs it; the one for keys can be
+    // far more costly than the maze's size suggests.
+    let solvable = if policy.require_solvable || policy.require_unique_solution {
+        check_keys(map)?;
+        let solvable = KeyCollector.solve(map).is_some();
+        if !solvable {
+            problems.push(format!(
+                "the end at {} cannot be reached from the start at {}",
+                at(map.end),
+                at(map.start)
+            ));
+        }
+        solvable
+    } else {
+        false
+    };
+    if policy.forbid_isolated_regions || policy.require_unique_solution {
+        let links = links(map);
+        if policy.forbid_isolated_regions {
+            problems.extend(isolated_regions(map, &links));
+        }
+        if policy.require_unique_solution && solvable {
+            problems.extend(second_route(map, &links));
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+// Cells connected by a step either way, walking through doors.
+fn links(map: &Map) -> HashMap<Position, Vec<Position>> {
+    // Sorted so messages name the same cells every time.
+    analysis::links(map, &|cell| *cell != Cell::Wall)
+        .into_iter()
+        .map(|(pos, neighbors)| {
+            let mut neighbors: Vec<Position> = neighbors.into_iter().collect();
+            neighbors.sort();
+            (pos, neighbors)
+        })
+        .collect()
+}
+
+// Breadth-first from `from`; each reached cell with the cell it was
+// reached from.
+fn reach(
+    links: &HashMap<Position, Vec<Position>>,
+    from: Position,
+) -> HashMap<Position, Option<Position>> {
+    let mut parent = HashMap::from([(from, None)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(current) = queue.pop_front() {
+        for &next in &links[&current] {
+            if let Entry::Vacant(entry) = parent.entry(next) {
+                entry.insert(Some(current));
+                queue.push_back(next);
+            }
+        }
+    }
+    parent
+}
+
+fn isolated_regions(map: &Map, links: &HashMap<Position, Vec<Position>>) -> Option<String> {
+    let mut seen: HashSet<Position> = reach(links, map.start).into_keys().collect();
+    let mut cells: Vec<Position> = links.keys().copied().collect();
+    cells.sort();
+
+    // Each region as its first cell in reading order and its size.
+    let mut regions = Vec::new();
+    for cell in cells {
+        if seen.contains(&cell) {
+            continue;
+        }
+        let region = reach(links, cell);
+        regions.push((cell, region.len()));
+        seen.extend(region.into_keys());
+    }
+    if regions.is_empty() {
+        return None;
+    }
+
+    let mut listed: Vec<String> = regions
+        .iter()
+        .take(MAX_REPORTED)
+        .map(|&(first, size)| match size {
+            1 => format!("the cell at {}", at(first)),
+            size => format!("{} cells from {}", size, at(first)),
+        })
+        .collect();
+    if regions.len() > MAX_REPORTED {
+        listed.push(format!("{} more", regions.len() - MAX_REPORTED));
+    }
+    Some(format!(
+        "{} cannot be reached from the start: {}",
+        match regions.len() {
+            1 => "1 open region".to_string(),
+            count => format!("{} open regions", count),
+        },
+        listed.join(", ")
+    ))
+}
+
+// A route is the only one exactly when every step on it is a bridge, a
+// link whose removal would cut the maze in two. A step that is not a
+// bridge lies on a loop that offers a way around it.
+fn second_route(map: &Map, links: &HashMap<Position, Vec<Position>>) -> Option<String> {
+    let parent = reach(links, map.start);
+    let bridges = bridges(links, map.start);
+
+    let mut route = vec![map.end];
+    while let Some(&Some(previous)) = route.last().and_then(|pos| parent.get(pos)) {
+        route.push(previous);
+    }
+    route.reverse();
+
+    route
+        .windows(2)
+        .find(|step| !bridges.contains(&(step[0], step[1])))
+        .map(|step| {
+            format!(
+                "more than one route leads from start to end; the step from {} to {} \
+                 lies on a loop",
+                at(step[0]),
+                at(step[1])
+            )
+        })
+}
+
+// Bridges of the component holding `root`, each in both directions.
+// Tarjan's algorithm, kept iterative so large mazes cannot overflow the
+// stack.
+fn bridges(
+    links: &HashMap<Position, Vec<Position>>,
+    root: Position,
+) -> HashSet<(Position, Position)> {
+    let mut order = HashMap::from([(root, 0)]);
+    let mut low = HashMap::from([(root, 0)]);
+    let mut bridges = HashSet::new();
+    // Each cell on the current path with its parent and next link to try.
+    let mut stack: Vec<(Position, Option<Position>, usize)> = vec![(root, None, 0)];
+
+    while let Some(top) = stack.last_mut() {
+        let (current, parent, next) = *top;
+        if let Some(&child) = links[&current].get(next) {
+            top.2 += 1;
+            if Some(child) == parent {
+                continue;
+            }
+            match order.get(&child) {
+                Some(&seen) => {
+                    let current_low = low[&current];
+                    low.insert(current, current_low.min(seen));
+                }
+                None => {
+                    let number = order.len();
+                    order.insert(child, number);
+                    low.insert(child, number);
+                    stack.push((child, Some(current), 0));
+                }
+            }
+            continue;
+        }
+
+        stack.pop();
+        if let Some(parent) = parent {
+            let current_low = low[&current];
+            let parent_low = low[&parent];
+            low.insert(parent, parent_low.min(current_low));
+            if current_low > order[&parent] {
+                bridges.insert((parent, current));
+                bridges.insert((current, parent));
+            }
+        }
+    }
+    bridges
+}
+
+fn at(pos: Position) -> String {
+    format!("({}, {})", pos.row, pos.col)
+}
@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::Topology;
+use crate::entities::MazeValidation;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateMazeRequest {
     pub name: String,
     pub content: String,
+    pub topology: Option<Topology>,
+    pub validation: Option<MazeValidation>,
 }
-
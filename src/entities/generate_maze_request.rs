@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{GenerationAlgorithm, Placement};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenerateMazeRequest {
+    pub name: Option<String>,
+    pub algorithm: Option<GenerationAlgorithm>,
+    pub rows: usize,
+    pub cols: usize,
+    pub seed: Option<u64>,
+    pub toroidal: Option<bool>,
+    pub placement: Option<Placement>,
+}
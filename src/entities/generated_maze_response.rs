@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{GenerationAlgorithm, Placement};
+use crate::entities::MazeResponse;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerationParameters {
+    pub algorithm: GenerationAlgorithm,
+    pub rows: usize,
+    pub cols: usize,
+    pub seed: u64,
+    pub toroidal: bool,
+    pub placement: Placement,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GeneratedMazeResponse {
+    #[serde(flatten)]
+    pub maze: MazeResponse,
+    pub generation: GenerationParameters,
+}
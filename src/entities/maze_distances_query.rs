@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::Position;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MazeDistancesQuery {
+    // `row,col` of the cell to measure from.
+    pub from: String,
+}
+
+impl MazeDistancesQuery {
+    pub fn position(&self) -> Result<Position, String> {
+        let (row, col) = self
+            .from
+            .split_once(',')
+            .ok_or_else(|| format!("expected `row,col`, got '{}'", self.from))?;
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("invalid coordinate '{}'", value))
+        };
+        Ok(Position {
+            row: parse(row)?,
+            col: parse(col)?,
+        })
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::Position;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MazeDistancesResponse {
+    pub id: Uuid,
+    pub revision: u32,
+    pub from: Position,
+    // Steps to the reachable cell furthest away.
+    pub max_distance: u32,
+    // One row per maze row; `null` for walls and unreachable cells.
+    pub distances: Vec<Vec<Option<u32>>>,
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MazeListQuery {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub cursor: Option<String>,
+    pub name: Option<String>,
+    pub min_rows: Option<usize>,
+    pub max_rows: Option<usize>,
+    pub min_cols: Option<usize>,
+    pub max_cols: Option<usize>,
+    pub min_difficulty: Option<u32>,
+    pub max_difficulty: Option<u32>,
+    pub min_dead_ends: Option<usize>,
+    pub max_dead_ends: Option<usize>,
+    pub created_after: Option<DateTime<Utc>>,
+    pub created_before: Option<DateTime<Utc>>,
+    pub sort: Option<MazeSort>,
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MazeSort {
+    #[default]
+    CreatedAt,
+    Name,
+    // Number of cells, rows times columns.
+    Size,
+    // Unsolvable and unanalyzed mazes count as easier than any other.
+    Difficulty,
+}
+
+impl MazeSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MazeSort::CreatedAt => "created_at",
+            MazeSort::Name => "name",
+            MazeSort::Size => "size",
+            MazeSort::Difficulty => "difficulty",
+        }
+    }
+
+    // Newest first, everything else alphabetical or smallest first.
+    pub fn default_order(&self) -> SortOrder {
+        match self {
+            MazeSort::CreatedAt => SortOrder::Desc,
+            MazeSort::Name | MazeSort::Size | MazeSort::Difficulty => SortOrder::Asc,
+        }
+    }
+}
+
+impl fmt::Display for MazeSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
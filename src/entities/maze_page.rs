@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use super::MazeSummary;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MazePage {
+    pub items: Vec<MazeSummary>,
+    // Pass as `cursor` to fetch the next page; absent on the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
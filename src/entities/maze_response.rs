@@ -1,11 +1,16 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::domain::Topology;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MazeResponse {
     pub id: Uuid,
     pub name: String,
     pub content: String,
+    pub topology: Topology,
+    // Starts at 1 and goes up with every update.
+    pub revision: u32,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
 }
-
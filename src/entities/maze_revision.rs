@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::Topology;
+
+// An earlier version of a maze, as it was before an update replaced it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MazeRevision {
+    pub revision: u32,
+    pub name: String,
+    pub content: String,
+    pub topology: Topology,
+    // When this version was saved.
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
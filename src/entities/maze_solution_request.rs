@@ -1,10 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use crate::domain::{Algorithm, MoveSet};
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MazeSolutionRequest {
     pub player_row: usize,
     pub player_col: usize,
     pub portal_row: usize,
     pub portal_col: usize,
+    pub algorithm: Option<Algorithm>,
+    pub moves: Option<MoveSet>,
+    pub include_ascii: Option<bool>,
 }
-
@@ -1,10 +1,20 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::domain::{Algorithm, Position, Step};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MazeSolutionResponse {
     pub id: Uuid,
     pub name: String,
-    pub solution: String,
+    pub algorithm: Algorithm,
+    pub path: Vec<Position>,
+    pub steps: usize,
+    pub moves: Vec<Step>,
+    pub explored: usize,
+    // Keys picked up along the path, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<char>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution: Option<String>,
 }
-
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::MazeStats;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MazeStatsResponse {
+    pub id: Uuid,
+    pub revision: u32,
+    #[serde(flatten)]
+    pub stats: MazeStats,
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::Topology;
+
+// A list entry: everything about a maze except its content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MazeSummary {
+    pub id: Uuid,
+    pub name: String,
+    pub topology: Topology,
+    pub rows: usize,
+    pub cols: usize,
+    // From the maze analysis; `None` when it is not known, and difficulty
+    // also for mazes that cannot be solved.
+    pub difficulty: Option<u32>,
+    pub dead_ends: Option<usize>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
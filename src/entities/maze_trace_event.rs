@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Position, TraceFrame};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MazeTraceEvent {
+    // One per expanded cell, in the order the solver expanded them.
+    Expand(TraceFrame),
+    // Always the last event.
+    Done {
+        path: Option<Vec<Position>>,
+        explored: usize,
+    },
+}
+
+impl MazeTraceEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MazeTraceEvent::Expand(_) => "expand",
+            MazeTraceEvent::Done { .. } => "done",
+        }
+    }
+}
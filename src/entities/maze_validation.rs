@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::ValidationPolicy;
+
+// Checks a new maze has to pass before it is stored. Everything left out
+// is not checked.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MazeValidation {
+    pub require_solvable: Option<bool>,
+    pub forbid_isolated_regions: Option<bool>,
+    pub require_unique_solution: Option<bool>,
+    pub max_rows: Option<usize>,
+    pub max_cols: Option<usize>,
+}
+
+impl MazeValidation {
+    pub fn policy(&self) -> ValidationPolicy {
+        ValidationPolicy {
+            require_solvable: self.require_solvable.unwrap_or(false),
+            forbid_isolated_regions: self.forbid_isolated_regions.unwrap_or(false),
+            require_unique_solution: self.require_unique_solution.unwrap_or(false),
+            max_rows: self.max_rows,
+            max_cols: self.max_cols,
+        }
+    }
+}
@@ -1,11 +1,47 @@
 pub mod create_maze_request;
 pub mod error_response;
+pub mod generate_maze_request;
+pub mod generated_maze_response;
+pub mod maze_distances_query;
+pub mod maze_distances_response;
+pub mod maze_list_query;
+pub mod maze_page;
 pub mod maze_response;
+pub mod maze_revision;
 pub mod maze_solution_request;
 pub mod maze_solution_response;
+pub mod maze_stats_response;
+pub mod maze_summary;
+pub mod maze_trace_event;
+pub mod maze_validation;
+pub mod patch_maze_request;
+pub mod race_client_message;
+pub mod race_player;
+pub mod race_query;
+pub mod race_server_message;
+pub mod render_query;
+pub mod update_maze_request;
 
 pub use create_maze_request::CreateMazeRequest;
 pub use error_response::ErrorResponse;
+pub use generate_maze_request::GenerateMazeRequest;
+pub use generated_maze_response::{GeneratedMazeResponse, GenerationParameters};
+pub use maze_distances_query::MazeDistancesQuery;
+pub use maze_distances_response::MazeDistancesResponse;
+pub use maze_list_query::{MazeListQuery, MazeSort, SortOrder};
+pub use maze_page::MazePage;
 pub use maze_response::MazeResponse;
+pub use maze_revision::MazeRevision;
 pub use maze_solution_request::MazeSolutionRequest;
 pub use maze_solution_response::MazeSolutionResponse;
+pub use maze_stats_response::MazeStatsResponse;
+pub use maze_summary::MazeSummary;
+pub use maze_trace_event::MazeTraceEvent;
+pub use maze_validation::MazeValidation;
+pub use patch_maze_request::PatchMazeRequest;
+pub use race_client_message::RaceClientMessage;
+pub use race_player::RacePlayer;
+pub use race_query::RaceQuery;
+pub use race_server_message::RaceServerMessage;
+pub use render_query::RenderQuery;
+pub use update_maze_request::UpdateMazeRequest;
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::Topology;
+
+// Fields left out keep their current value.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PatchMazeRequest {
+    pub name: Option<String>,
+    pub content: Option<String>,
+    pub topology: Option<Topology>,
+}
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::play::Direction;
+use crate::domain::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RaceClientMessage {
+    // One step up, down, left or right.
+    Step { direction: Direction },
+    // A move to any cell the maze's move set allows from the current one.
+    Move { to: Position },
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RacePlayer {
+    pub id: u32,
+    pub name: String,
+    pub position: Position,
+    pub moves: usize,
+    // Place in the finish ranking, from 1; absent until the player finishes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<usize>,
+    // Milliseconds from joining to finishing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_ms: Option<u64>,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RaceQuery {
+    pub name: String,
+    // Players racing the same maze in the same session see each other.
+    pub session: Option<String>,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::RacePlayer;
+use crate::domain::Position;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RaceServerMessage {
+    // Sent once, right after joining.
+    Welcome {
+        player: u32,
+        session: String,
+        maze_id: Uuid,
+        revision: u32,
+        start: Position,
+        end: Position,
+    },
+    // Sent to everyone whenever a player joins, moves or leaves. Finished
+    // players come first, in ranking order.
+    State {
+        players: Vec<RacePlayer>,
+    },
+    // Sent to everyone once every player in the session has finished.
+    Finished {
+        ranking: Vec<RacePlayer>,
+    },
+    // Sent only to the player whose message was refused.
+    Rejected {
+        reason: String,
+    },
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Color, Overlay, RenderFormat, RenderOptions};
+
+// Picks an image or text rendering instead of JSON, and how to draw it.
+// Everything left out keeps its default.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RenderQuery {
+    pub format: Option<RenderFormat>,
+    pub cell_size: Option<u32>,
+    pub wall: Option<Color>,
+    pub floor: Option<Color>,
+    pub start: Option<Color>,
+    pub end: Option<Color>,
+    pub path_start: Option<Color>,
+    pub path_end: Option<Color>,
+    pub overlay: Option<Overlay>,
+}
+
+impl RenderQuery {
+    pub fn options(&self) -> RenderOptions {
+        let defaults = RenderOptions::default();
+        RenderOptions {
+            cell_size: self.cell_size.unwrap_or(defaults.cell_size),
+            wall: self.wall.unwrap_or(defaults.wall),
+            floor: self.floor.unwrap_or(defaults.floor),
+            start: self.start.unwrap_or(defaults.start),
+            end: self.end.unwrap_or(defaults.end),
+            path_start: self.path_start.unwrap_or(defaults.path_start),
+            path_end: self.path_end.unwrap_or(defaults.path_end),
+            overlay: self.overlay.unwrap_or(defaults.overlay),
+        }
+    }
+}
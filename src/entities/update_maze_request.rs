@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::Topology;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateMazeRequest {
+    pub name: String,
+    pub content: String,
+    pub topology: Option<Topology>,
+}
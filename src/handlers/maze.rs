@@ -1,100 +1,415 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{Json},
-    routing::{delete, get, post},
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderName, StatusCode},
+    response::{
+        sse::{Event, Sse},
+        IntoResponse, Json, Response,
+    },
+    routing::{delete, get, patch, post, put},
     Router,
 };
+use futures_util::StreamExt;
+use std::ops::ControlFlow;
 use uuid::Uuid;
 
 use crate::{
-    db::MazeRepository,
-    entities::{CreateMazeRequest, MazeResponse, MazeSolutionRequest, MazeSolutionResponse},
+    db::{CachedSolution, Cursor, MazeFilter, MazeStore, SolutionKey},
+    domain::{
+        analyze, generate, pathfinding, render, DistanceField, GenerationOptions, Map, MoveSet,
+        Position, RenderFormat,
+    },
+    entities::{
+        CreateMazeRequest, GenerateMazeRequest, GeneratedMazeResponse, GenerationParameters,
+        MazeDistancesQuery, MazeDistancesResponse, MazeListQuery, MazePage, MazeResponse,
+        MazeRevision, MazeSolutionRequest, MazeSolutionResponse, MazeStatsResponse, MazeTraceEvent,
+        PatchMazeRequest, RenderQuery, UpdateMazeRequest,
+    },
     errors::AppError,
 };
 
 type ApiResult<T> = Result<Json<T>, AppError>;
 
-pub fn create_router(repository: MazeRepository) -> Router {
+const MAX_GENERATED_SIDE: usize = 256;
+const MAX_CELL_SIZE: u32 = 64;
+const MAX_IMAGE_PIXELS: usize = 16 * 1024 * 1024;
+// Trace frames computed ahead of a slow client before the search waits.
+const TRACE_BUFFER: usize = 64;
+
+pub fn create_router<S: MazeStore>(repository: S) -> Router {
     Router::new()
-        .route("/api/mazes/:id", get(get_maze))
-        .route("/api/mazes/:id", delete(delete_maze))
-        .route("/api/mazes/:id/solution", post(get_maze_solution))
-        .route("/api/mazes", get(get_all_mazes))
-        .route("/api/mazes", post(create_maze))
-        .with_state(repository)
+        .route("/api/mazes/:id", get(get_maze::<S>))
+        .route("/api/mazes/:id", delete(delete_maze::<S>))
+        .route("/api/mazes/:id", put(update_maze::<S>))
+        .route("/api/mazes/:id", patch(patch_maze::<S>))
+        .route("/api/mazes/:id/revisions", get(get_maze_revisions::<S>))
+        .route(
+            "/api/mazes/:id/revisions/:revision/rollback",
+            post(rollback_maze::<S>),
+        )
+        .route("/api/mazes/:id/solution", post(get_maze_solution::<S>))
+        .route("/api/mazes/:id/solution/trace", post(trace_maze_solution::<S>))
+        .route("/api/mazes/:id/distances", get(get_maze_distances::<S>))
+        .route("/api/mazes/:id/stats", get(get_maze_stats::<S>))
+        .route("/api/mazes", get(get_all_mazes::<S>))
+        .route("/api/mazes", post(create_maze::<S>))
+        .route("/api/mazes/generate", post(generate_maze::<S>))
+        .with_state(repository.clone())
+        .merge(super::race::router(repository))
 }
 
-async fn get_maze(
-    State(repository): State<MazeRepository>,
+async fn get_maze<S: MazeStore>(
+    State(repository): State<S>,
     Path(id): Path<Uuid>,
-) -> ApiResult<MazeResponse> {
-    repository
+    Query(render_query): Query<RenderQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let maze = repository
         .get_by_id(id)
         .await
         .map_err(|_| AppError::internal("database error"))?
-        .map(Json)
-        .ok_or_else(|| AppError::not_found("maze not found"))
+        .ok_or_else(|| AppError::not_found("maze not found"))?;
+
+    match render_format(&render_query, &headers) {
+        Some(format) => {
+            let mut map = Map::parse_from_string(&maze.content)
+                .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
+            map.topology = maze.topology;
+            render_maze(&map, &[], format, &render_query)
+        }
+        None => Ok(Json(maze).into_response()),
+    }
 }
 
-async fn get_all_mazes(
-    State(repository): State<MazeRepository>,
-) -> ApiResult<Vec<MazeResponse>> {
-    repository
-        .get_all()
+// `?format=` wins over the Accept header; JSON is the default.
+fn render_format(query: &RenderQuery, headers: &HeaderMap) -> Option<RenderFormat> {
+    query.format.or_else(|| {
+        headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .find_map(|media| RenderFormat::from_content_type(media.split(';').next()?.trim()))
+    })
+}
+
+fn render_maze(
+    map: &Map,
+    path: &[Position],
+    format: RenderFormat,
+    query: &RenderQuery,
+) -> Result<Response, AppError> {
+    let options = query.options();
+    if format.is_image() {
+        if options.cell_size == 0 || options.cell_size > MAX_CELL_SIZE {
+            return Err(AppError::bad_request(format!(
+                "cell_size must be between 1 and {}",
+                MAX_CELL_SIZE
+            )));
+        }
+        let cell_pixels = (options.cell_size * options.cell_size) as usize;
+        if map.rows * map.cols * cell_pixels > MAX_IMAGE_PIXELS {
+            return Err(AppError::bad_request("image too large, use a smaller cell_size"));
+        }
+    }
+
+    let rendered = render(map, path, format, &options)
+        .map_err(|e| AppError::internal(format!("failed to render maze: {}", e)))?;
+    Ok(([(header::CONTENT_TYPE, format.content_type())], rendered).into_response())
+}
+
+async fn get_all_mazes<S: MazeStore>(
+    State(repository): State<S>,
+    Query(query): Query<MazeListQuery>,
+) -> ApiResult<MazePage> {
+    let filter = MazeFilter::try_from(query).map_err(AppError::bad_request)?;
+
+    // One extra entry tells whether there is a next page.
+    let mut items = repository
+        .list(&MazeFilter {
+            limit: filter.limit + 1,
+            ..filter.clone()
+        })
         .await
-        .map(Json)
-        .map_err(|_| AppError::internal("database error"))
+        .map_err(|_| AppError::internal("database error"))?;
+
+    let next_cursor = if items.len() > filter.limit {
+        items.truncate(filter.limit);
+        items.last().map(|last| Cursor::after(last, filter.sort).encode())
+    } else {
+        None
+    };
+
+    Ok(Json(MazePage { items, next_cursor }))
 }
 
-async fn get_maze_solution(
-    State(repository): State<MazeRepository>,
+async fn get_maze_solution<S: MazeStore>(
+    State(repository): State<S>,
     Path(id): Path<Uuid>,
+    Query(render_query): Query<RenderQuery>,
+    headers: HeaderMap,
     Json(request): Json<MazeSolutionRequest>,
-) -> ApiResult<MazeSolutionResponse> {
+) -> Result<Response, AppError> {
     let maze = repository
         .get_by_id(id)
         .await
         .map_err(|_| AppError::internal("database error"))?
         .ok_or_else(|| AppError::not_found("maze not found"))?;
 
-    let mut map = crate::domain::Map::parse_from_string(&maze.content)
-        .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
+    let moves = request.moves.clone().unwrap_or_default();
+    let key = solution_key(&maze, &request, &moves)?;
 
-    let player_pos = crate::domain::Position {
-        row: request.player_row,
-        col: request.player_col,
+    // A failing cache only costs a recomputation, so its errors are dropped.
+    let cached = repository.cached_solution(&key).await.unwrap_or_default();
+    let (cached, hit) = match cached {
+        Some(cached) => (cached, "hit"),
+        None => {
+            let solved = solve(&maze, &key, moves)?;
+            repository.cache_solution(&key, &solved).await.ok();
+            (solved, "miss")
+        }
     };
-    let portal_pos = crate::domain::Position {
-        row: request.portal_row,
-        col: request.portal_col,
+
+    let CachedSolution::Solved(mut solution) = cached else {
+        return Err(AppError::bad_request("no path found"));
     };
+    let cache_header = [(HeaderName::from_static("x-cache"), hit)];
+
+    if let Some(format) = render_format(&render_query, &headers) {
+        let map = solution_map(&maze, &key)?;
+        let rendered = render_maze(&map, &solution.path, format, &render_query)?;
+        return Ok((cache_header, rendered).into_response());
+    }
+    if !request.include_ascii.unwrap_or(true) {
+        solution.solution = None;
+    }
+    Ok((cache_header, Json(solution)).into_response())
+}
+
+fn solution_key(
+    maze: &MazeResponse,
+    request: &MazeSolutionRequest,
+    moves: &MoveSet,
+) -> Result<SolutionKey, AppError> {
+    Ok(SolutionKey {
+        maze_id: maze.id,
+        revision: maze.revision,
+        start: Position {
+            row: request.player_row,
+            col: request.player_col,
+        },
+        end: Position {
+            row: request.portal_row,
+            col: request.portal_col,
+        },
+        algorithm: request.algorithm.unwrap_or_default(),
+        options: serde_json::to_string(moves)
+            .map_err(|_| AppError::internal("failed to serialize moves"))?,
+    })
+}
+
+// The maze with the requested start and end in place.
+fn solution_map(maze: &MazeResponse, key: &SolutionKey) -> Result<Map, AppError> {
+    let mut map = Map::parse_from_string(&maze.content)
+        .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
 
-    if player_pos.row >= map.rows || player_pos.col >= map.cols {
+    if key.start.row >= map.rows || key.start.col >= map.cols {
         return Err(AppError::bad_request("invalid player coordinates"));
     }
-    if portal_pos.row >= map.rows || portal_pos.col >= map.cols {
+    if key.end.row >= map.rows || key.end.col >= map.cols {
         return Err(AppError::bad_request("invalid portal coordinates"));
     }
 
-    map.start = player_pos;
-    map.end = portal_pos;
+    map.start = key.start;
+    map.end = key.end;
+    map.topology = maze.topology;
+    Ok(map)
+}
+
+// The map a search runs on: the requested start, end and move set.
+fn search_map(maze: &MazeResponse, key: &SolutionKey, moves: MoveSet) -> Result<Map, AppError> {
+    let mut map = solution_map(maze, key)?;
+    map.moves = moves;
+    map.moves
+        .validate()
+        .map_err(|e| AppError::bad_request(format!("invalid moves: {}", e)))?;
+    Ok(map)
+}
 
-    let path = crate::domain::find_path(&map)
-        .ok_or_else(|| AppError::bad_request("no path found"))?;
+// Runs the search for a cache miss. The ASCII rendering is always
+// included so the cached entry can serve either kind of request.
+fn solve(
+    maze: &MazeResponse,
+    key: &SolutionKey,
+    moves: MoveSet,
+) -> Result<CachedSolution, AppError> {
+    let mut map = search_map(maze, key, moves)?;
+    if key.algorithm == pathfinding::Algorithm::KeyCollector {
+        pathfinding::check_keys(&map).map_err(AppError::bad_request)?;
+    }
+    let result = key.algorithm.solver().search(&map);
+    let Some(path) = result.path else {
+        return Ok(CachedSolution::Unsolvable);
+    };
+    let moves = crate::domain::route::describe(&map, &path)
+        .ok_or_else(|| AppError::internal("solver returned a disconnected path"))?;
+    let keys = crate::domain::collected_keys(&map, &path);
 
     map.mark_path(&path);
-    let solution = map.to_string();
+    Ok(CachedSolution::Solved(MazeSolutionResponse {
+        id: maze.id,
+        name: maze.name.clone(),
+        algorithm: key.algorithm,
+        steps: moves.len(),
+        path,
+        moves,
+        explored: result.explored,
+        keys,
+        solution: Some(map.to_string()),
+    }))
+}
+
+// Streams every expansion of a search as it happens: server-sent events when
+// the client accepts `text/event-stream`, newline-delimited JSON otherwise.
+async fn trace_maze_solution<S: MazeStore>(
+    State(repository): State<S>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(request): Json<MazeSolutionRequest>,
+) -> Result<Response, AppError> {
+    let maze = repository
+        .get_by_id(id)
+        .await
+        .map_err(|_| AppError::internal("database error"))?
+        .ok_or_else(|| AppError::not_found("maze not found"))?;
+
+    let moves = request.moves.clone().unwrap_or_default();
+    let key = solution_key(&maze, &request, &moves)?;
+    let map = search_map(&maze, &key, moves)?;
+    pathfinding::check_trace(&map, key.algorithm).map_err(AppError::bad_request)?;
+
+    // Once the client goes away sends fail, which stops the search.
+    let (tx, rx) = tokio::sync::mpsc::channel(TRACE_BUFFER);
+    tokio::task::spawn_blocking(move || {
+        let traced = pathfinding::trace(&map, key.algorithm, |frame| {
+            match tx.blocking_send(MazeTraceEvent::Expand(frame)) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(_) => ControlFlow::Break(()),
+            }
+        });
+        if let Ok(Some(result)) = traced {
+            tx.blocking_send(MazeTraceEvent::Done {
+                path: result.path,
+                explored: result.explored,
+            })
+            .ok();
+        }
+    });
+    let events = futures_util::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (event, rx))
+    });
+
+    if accepts(&headers, "text/event-stream") {
+        let events = events.map(|event| Event::default().event(event.kind()).json_data(&event));
+        return Ok(Sse::new(events).into_response());
+    }
+    let lines = events.map(|event| {
+        serde_json::to_vec(&event).map(|mut line| {
+            line.push(b'\n');
+            line
+        })
+    });
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response())
+}
+
+fn accepts(headers: &HeaderMap, media_type: &str) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|media| media.split(';').next().is_some_and(|m| m.trim() == media_type))
+}
+
+async fn get_maze_distances<S: MazeStore>(
+    State(repository): State<S>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<MazeDistancesQuery>,
+) -> Result<([(HeaderName, &'static str); 1], Json<MazeDistancesResponse>), AppError> {
+    let from = query.position().map_err(AppError::bad_request)?;
+    let maze = repository
+        .get_by_id(id)
+        .await
+        .map_err(|_| AppError::internal("database error"))?
+        .ok_or_else(|| AppError::not_found("maze not found"))?;
+
+    // As for solutions, cache errors only cost a recomputation.
+    let cached = repository
+        .cached_distances(maze.id, maze.revision, from)
+        .await
+        .unwrap_or_default();
+    let (field, hit) = match cached {
+        Some(field) => (field, "hit"),
+        None => {
+            let mut map = crate::domain::Map::parse_from_string(&maze.content)
+                .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
+            if from.row >= map.rows || from.col >= map.cols {
+                return Err(AppError::bad_request("invalid from coordinates"));
+            }
+            if !map.grid[from.row][from.col].is_passable() {
+                return Err(AppError::bad_request("from must be an open cell"));
+            }
+            map.topology = maze.topology;
+
+            let field = DistanceField::compute(&map, from);
+            repository
+                .cache_distances(maze.id, maze.revision, &field)
+                .await
+                .ok();
+            (field, "miss")
+        }
+    };
 
-    Ok(Json(MazeSolutionResponse {
+    let max_distance = field.farthest().map_or(0, |(_, distance)| distance);
+    Ok((
+        [(HeaderName::from_static("x-cache"), hit)],
+        Json(MazeDistancesResponse {
+            id: maze.id,
+            revision: maze.revision,
+            from: field.from,
+            max_distance,
+            distances: field.distances,
+        }),
+    ))
+}
+
+async fn get_maze_stats<S: MazeStore>(
+    State(repository): State<S>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<MazeStatsResponse> {
+    let maze = repository
+        .get_by_id(id)
+        .await
+        .map_err(|_| AppError::internal("database error"))?
+        .ok_or_else(|| AppError::not_found("maze not found"))?;
+
+    let mut map = Map::parse_from_string(&maze.content)
+        .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
+    map.topology = maze.topology;
+
+    Ok(Json(MazeStatsResponse {
         id: maze.id,
-        name: maze.name,
-        solution,
+        revision: maze.revision,
+        stats: analyze(&map),
     }))
 }
 
-async fn delete_maze(
-    State(repository): State<MazeRepository>,
+async fn delete_maze<S: MazeStore>(
+    State(repository): State<S>,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, AppError> {
     let deleted = repository
@@ -109,17 +424,157 @@ async fn delete_maze(
     }
 }
 
-async fn create_maze(
-    State(repository): State<MazeRepository>,
+async fn create_maze<S: MazeStore>(
+    State(repository): State<S>,
     Json(request): Json<CreateMazeRequest>,
 ) -> ApiResult<MazeResponse> {
-    crate::domain::Map::parse_from_string(&request.content)
+    let mut map = crate::domain::Map::parse_from_string(&request.content)
         .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
+    map.topology = request.topology.unwrap_or_default();
+    let topology = map.topology;
+    if let Some(validation) = &request.validation {
+        // The rules may search the whole maze, which is kept off the async
+        // runtime like a trace.
+        let policy = validation.policy();
+        tokio::task::spawn_blocking(move || crate::domain::validate(&map, &policy))
+            .await
+            .map_err(|_| AppError::internal("validation failed"))?
+            .map_err(|e| AppError::bad_request(format!("maze failed validation: {}", e)))?;
+    }
+
+    repository
+        .create(&request.name, &request.content, topology)
+        .await
+        .map(Json)
+        .map_err(|_| AppError::internal("database error"))
+}
+
+
+async fn update_maze<S: MazeStore>(
+    State(repository): State<S>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateMazeRequest>,
+) -> ApiResult<MazeResponse> {
+    let topology = request.topology.unwrap_or_default();
+    save_maze(&repository, id, &request.name, &request.content, topology).await
+}
+
+async fn patch_maze<S: MazeStore>(
+    State(repository): State<S>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<PatchMazeRequest>,
+) -> ApiResult<MazeResponse> {
+    let maze = repository
+        .get_by_id(id)
+        .await
+        .map_err(|_| AppError::internal("database error"))?
+        .ok_or_else(|| AppError::not_found("maze not found"))?;
+
+    let name = request.name.unwrap_or(maze.name);
+    let content = request.content.unwrap_or(maze.content);
+    let topology = request.topology.unwrap_or(maze.topology);
+    save_maze(&repository, id, &name, &content, topology).await
+}
+
+async fn get_maze_revisions<S: MazeStore>(
+    State(repository): State<S>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Vec<MazeRevision>> {
+    repository
+        .get_by_id(id)
+        .await
+        .map_err(|_| AppError::internal("database error"))?
+        .ok_or_else(|| AppError::not_found("maze not found"))?;
 
     repository
-        .create(&request.name, &request.content)
+        .revisions(id)
         .await
         .map(Json)
         .map_err(|_| AppError::internal("database error"))
 }
 
+// Rolling back saves the old version as a new revision, so the history
+// only ever grows and the rollback itself can be undone.
+async fn rollback_maze<S: MazeStore>(
+    State(repository): State<S>,
+    Path((id, revision)): Path<(Uuid, u32)>,
+) -> ApiResult<MazeResponse> {
+    let revision = repository
+        .get_revision(id, revision)
+        .await
+        .map_err(|_| AppError::internal("database error"))?
+        .ok_or_else(|| AppError::not_found("revision not found"))?;
+
+    save_maze(
+        &repository,
+        id,
+        &revision.name,
+        &revision.content,
+        revision.topology,
+    )
+    .await
+}
+
+async fn save_maze<S: MazeStore>(
+    repository: &S,
+    id: Uuid,
+    name: &str,
+    content: &str,
+    topology: crate::domain::Topology,
+) -> ApiResult<MazeResponse> {
+    crate::domain::Map::parse_from_string(content)
+        .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
+
+    repository
+        .update(id, name, content, topology)
+        .await
+        .map_err(|_| AppError::internal("database error"))?
+        .map(Json)
+        .ok_or_else(|| AppError::not_found("maze not found"))
+}
+
+async fn generate_maze<S: MazeStore>(
+    State(repository): State<S>,
+    Json(request): Json<GenerateMazeRequest>,
+) -> ApiResult<GeneratedMazeResponse> {
+    if request.rows > MAX_GENERATED_SIDE || request.cols > MAX_GENERATED_SIDE {
+        return Err(AppError::bad_request(format!(
+            "rows and cols must not exceed {}",
+            MAX_GENERATED_SIDE
+        )));
+    }
+
+    let generation = GenerationParameters {
+        algorithm: request.algorithm.unwrap_or_default(),
+        rows: request.rows,
+        cols: request.cols,
+        seed: request.seed.unwrap_or_else(|| Uuid::new_v4().as_u64_pair().0),
+        toroidal: request.toroidal.unwrap_or(false),
+        placement: request.placement.unwrap_or_default(),
+    };
+
+    let options = GenerationOptions {
+        rows: generation.rows,
+        cols: generation.cols,
+        seed: generation.seed,
+        toroidal: generation.toroidal,
+        placement: generation.placement,
+    };
+    let map = generate(generation.algorithm, &options)
+        .map_err(|e| AppError::bad_request(format!("invalid generation parameters: {}", e)))?;
+
+    let name = request.name.unwrap_or_else(|| {
+        format!(
+            "{} {}x{} ({})",
+            generation.algorithm, generation.rows, generation.cols, generation.seed
+        )
+    });
+    let content = map.to_string();
+
+    let maze = repository
+        .create(&name, content.trim_end_matches('\n'), map.topology)
+        .await
+        .map_err(|_| AppError::internal("database error"))?;
+
+    Ok(Json(GeneratedMazeResponse { maze, generation }))
+}
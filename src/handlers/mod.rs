@@ -1,4 +1,5 @@
 pub mod maze;
+pub mod race;
 
 pub use maze::create_router;
 
@@ -0,0 +1,339 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    response::Response,
+    routing::get,
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::{
+    db::MazeStore,
+    domain::{Map, Position},
+    entities::{RaceClientMessage, RacePlayer, RaceQuery, RaceServerMessage},
+    errors::AppError,
+};
+
+const DEFAULT_SESSION: &str = "default";
+const MAX_NAME_LENGTH: usize = 64;
+const MAX_PLAYERS: usize = 32;
+
+pub fn router<S: MazeStore>(repository: S) -> Router {
+    Router::new()
+        .route("/api/mazes/:id/race", get(join_race::<S>))
+        .with_state(RaceState {
+            repository,
+            sessions: Arc::default(),
+        })
+}
+
+// Races in progress by maze and session name. They only live in memory and
+// are dropped when their last player leaves.
+type Sessions = Arc<Mutex<HashMap<(Uuid, String), Arc<Session>>>>;
+
+#[derive(Clone)]
+struct RaceState<S> {
+    repository: S,
+    sessions: Sessions,
+}
+
+struct Session {
+    name: String,
+    maze_id: Uuid,
+    // The race is run on the maze as it was when the session started.
+    revision: u32,
+    map: Map,
+    players: Mutex<Players>,
+    updates: broadcast::Sender<String>,
+}
+
+#[derive(Default)]
+struct Players {
+    next_id: u32,
+    players: Vec<Player>,
+    // How many of `players` have reached the end.
+    finished: usize,
+    // Whether `Finished` went out for the current set of players.
+    announced: bool,
+}
+
+struct Player {
+    state: RacePlayer,
+    joined: Instant,
+}
+
+async fn join_race<S: MazeStore>(
+    State(state): State<RaceState<S>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<RaceQuery>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, AppError> {
+    let name = query.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::bad_request(format!(
+            "name must be 1 to {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+    let session = query.session.as_deref().unwrap_or(DEFAULT_SESSION).trim();
+    if session.is_empty() || session.chars().count() > MAX_NAME_LENGTH {
+        return Err(AppError::bad_request(format!(
+            "session must be 1 to {} characters",
+            MAX_NAME_LENGTH
+        )));
+    }
+
+    // The seat is taken before the upgrade, so the player limit holds for
+    // joins that arrive together. If the upgrade never completes, the seat
+    // is dropped with the callback and given back.
+    let seat = state.join(id, session.to_string(), name).await?;
+    Ok(ws.on_upgrade(move |socket| race(socket, seat)))
+}
+
+impl<S: MazeStore> RaceState<S> {
+    async fn join(&self, id: Uuid, session: String, name: String) -> Result<Seat, AppError> {
+        let key = (id, session);
+        let found = self.sessions.lock().unwrap().get(&key).cloned();
+        let session = match found {
+            Some(session) => session,
+            None => Arc::new(self.load(&key).await?),
+        };
+
+        // Another player may have started the session while the maze loaded,
+        // or the one found above may have ended since. Joining under the
+        // same lock keeps a leaving player from removing it in between.
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.entry(key).or_insert(session).clone();
+        let id = session
+            .join(name)
+            .ok_or_else(|| AppError::bad_request("session is full"))?;
+        drop(sessions);
+
+        Ok(Seat {
+            sessions: self.sessions.clone(),
+            session,
+            id,
+        })
+    }
+
+    async fn load(&self, (id, name): &(Uuid, String)) -> Result<Session, AppError> {
+        let maze = self
+            .repository
+            .get_by_id(*id)
+            .await
+            .map_err(|_| AppError::internal("database error"))?
+            .ok_or_else(|| AppError::not_found("maze not found"))?;
+        let mut map = Map::parse_from_string(&maze.content)
+            .map_err(|e| AppError::bad_request(format!("invalid maze: {}", e)))?;
+        map.topology = maze.topology;
+
+        Ok(Session {
+            name: name.clone(),
+            maze_id: maze.id,
+            revision: maze.revision,
+            map,
+            players: Mutex::default(),
+            updates: broadcast::channel(64).0,
+        })
+    }
+}
+
+// A player's place in a session, given up when dropped: after the
+// connection ends, or with the upgrade callback if it never ran.
+struct Seat {
+    sessions: Sessions,
+    session: Arc<Session>,
+    id: u32,
+}
+
+impl Drop for Seat {
+    fn drop(&mut self) {
+        let mut sessions = self.sessions.lock().unwrap();
+        if self.session.leave(self.id) {
+            let key = (self.session.maze_id, self.session.name.clone());
+            if sessions
+                .get(&key)
+                .is_some_and(|current| Arc::ptr_eq(current, &self.session))
+            {
+                sessions.remove(&key);
+            }
+        } else {
+            drop(sessions);
+            self.session.publish();
+        }
+    }
+}
+
+async fn race(mut socket: WebSocket, seat: Seat) {
+    let (session, id) = (&seat.session, seat.id);
+    // Subscribing before publishing means the player also sees the state
+    // after their own join.
+    let mut updates = session.updates.subscribe();
+    let welcome = RaceServerMessage::Welcome {
+        player: id,
+        session: session.name.clone(),
+        maze_id: session.maze_id,
+        revision: session.revision,
+        start: session.map.start,
+        end: session.map.end,
+    };
+    if !send(&mut socket, &welcome).await {
+        return;
+    }
+
+    session.publish();
+    loop {
+        tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    if let Err(reason) = session.play(id, &text) {
+                        if !send(&mut socket, &RaceServerMessage::Rejected { reason }).await {
+                            break;
+                        }
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            update = updates.recv() => match update {
+                Ok(text) => {
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                // Every update is a full snapshot, so missed ones can
+                // be skipped.
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
+// Returns whether the message went out.
+async fn send(socket: &mut WebSocket, message: &RaceServerMessage) -> bool {
+    let Ok(text) = serde_json::to_string(message) else {
+        return false;
+    };
+    socket.send(Message::Text(text)).await.is_ok()
+}
+
+impl Session {
+    // `None` when the session is full.
+    fn join(&self, name: String) -> Option<u32> {
+        let mut players = self.players.lock().unwrap();
+        if players.players.len() >= MAX_PLAYERS {
+            return None;
+        }
+        players.next_id += 1;
+        let id = players.next_id;
+        players.players.push(Player {
+            state: RacePlayer {
+                id,
+                name,
+                position: self.map.start,
+                moves: 0,
+                rank: None,
+                time_ms: None,
+            },
+            joined: Instant::now(),
+        });
+        players.announced = false;
+        Some(id)
+    }
+
+    // Returns whether the session is now empty.
+    fn leave(&self, id: u32) -> bool {
+        let mut players = self.players.lock().unwrap();
+        let Some(index) = players.players.iter().position(|p| p.state.id == id) else {
+            return players.players.is_empty();
+        };
+        if players.players.remove(index).state.rank.is_some() {
+            players.finished -= 1;
+        }
+        players.players.is_empty()
+    }
+
+    // Applies one message from a player, or says why it was refused.
+    fn play(&self, id: u32, text: &str) -> Result<(), String> {
+        let message: RaceClientMessage =
+            serde_json::from_str(text).map_err(|e| format!("invalid message: {}", e))?;
+
+        {
+            let mut players = self.players.lock().unwrap();
+            // Ranks go on from those still here, so they stay distinct when
+            // finished players leave.
+            let rank = players
+                .players
+                .iter()
+                .filter_map(|player| player.state.rank)
+                .max()
+                .unwrap_or(0)
+                + 1;
+            let player = players
+                .players
+                .iter_mut()
+                .find(|player| player.state.id == id)
+                .ok_or("not in this session")?;
+            if player.state.rank.is_some() {
+                return Err("already finished".to_string());
+            }
+
+            let from = player.state.position;
+            let target = match message {
+                RaceClientMessage::Step { direction } => self.map.step(from, direction.offset()),
+                RaceClientMessage::Move { to } => Some(to),
+            };
+            let target = target
+                .filter(|to| self.map.get_neighbors(from).contains(to))
+                .ok_or_else(|| format!("illegal move from {}", describe(from)))?;
+
+            player.state.position = target;
+            player.state.moves += 1;
+            if target == self.map.end {
+                player.state.rank = Some(rank);
+                player.state.time_ms = Some(player.joined.elapsed().as_millis() as u64);
+                players.finished += 1;
+            }
+        }
+
+        self.publish();
+        Ok(())
+    }
+
+    // Sends the current state to every player, and the final ranking once
+    // all of them are through.
+    fn publish(&self) {
+        let mut players = self.players.lock().unwrap();
+        let mut snapshot: Vec<RacePlayer> = players
+            .players
+            .iter()
+            .map(|player| player.state.clone())
+            .collect();
+        snapshot.sort_by_key(|player| (player.rank.is_none(), player.rank, player.id));
+
+        let done = !snapshot.is_empty() && players.finished == snapshot.len();
+        let finished = (done && !players.announced).then(|| RaceServerMessage::Finished {
+            ranking: snapshot.clone(),
+        });
+        players.announced |= done;
+
+        // Sending fails only when nobody is listening, which is fine.
+        let state = RaceServerMessage::State { players: snapshot };
+        for message in std::iter::once(state).chain(finished) {
+            if let Ok(text) = serde_json::to_string(&message) {
+                let _ = self.updates.send(text);
+            }
+        }
+    }
+}
+
+fn describe(pos: Position) -> String {
+    format!("({}, {})", pos.row, pos.col)
+}
@@ -4,5 +4,5 @@ pub mod db;
 pub mod entities;
 pub mod errors;
 
-pub use domain::{Cell, Map, Position, find_path};
+pub use domain::{Algorithm, Cell, Map, MoveSet, Position, Solver, Topology, a_star, dijkstra, find_path};
 pub use errors::AppError;
@@ -0,0 +1,69 @@
+mod common;
+
+use common::bounded;
+use mazes::domain::analyze;
+use mazes::Topology;
+
+#[test]
+fn test_analyze_tree_maze() {
+    // One junction below the start, a dead end on either side of it.
+    let map = bounded("#i#\n   \n#O#");
+    let stats = analyze(&map);
+
+    assert_eq!(stats.open_cells, 5);
+    assert_eq!(stats.dead_ends, 2);
+    assert_eq!(stats.junctions, 1);
+    assert_eq!(stats.components, 1);
+    assert_eq!(stats.loops, 0);
+    assert_eq!(stats.diameter, 2);
+    assert!(stats.diameter_exact);
+    assert_eq!(stats.solution_length, Some(2));
+    assert_eq!(stats.solution_coverage, Some(0.4));
+    assert!(stats.difficulty.is_some());
+}
+
+#[test]
+fn test_analyze_loops_and_components() {
+    let map = bounded("i  #  \n   # O");
+    let stats = analyze(&map);
+
+    // Each open 2x2 block is one loop.
+    assert_eq!(stats.loops, 3);
+    assert_eq!(stats.components, 2);
+    assert_eq!(stats.solution_length, None);
+    assert_eq!(stats.difficulty, None);
+
+    // Wrapping closes the corridor into a ring.
+    let mut ring = bounded("i  O");
+    assert_eq!(analyze(&ring).loops, 0);
+    ring.topology = Topology::Torus;
+    let stats = analyze(&ring);
+    assert_eq!(stats.loops, 1);
+    assert_eq!(stats.dead_ends, 0);
+    assert_eq!(stats.solution_length, Some(1));
+    assert_eq!(stats.diameter, 2);
+}
+
+#[test]
+fn test_difficulty_ranks_winding_mazes_higher() {
+    let straight = analyze(&bounded("i     O"));
+    let winding = analyze(&bounded("i#   #\n # # #\n   # O"));
+
+    assert_eq!(straight.dead_ends, 0);
+    assert!(winding.difficulty.unwrap() > straight.difficulty.unwrap());
+    assert!(winding.difficulty.unwrap() <= 100);
+}
+
+#[test]
+fn test_analyze_large_maze_estimates_diameter() {
+    let row = " ".repeat(40);
+    let content = format!("i{}\n{}\n", &row[1..], row)
+        + &format!("{}\n", row).repeat(37)
+        + &format!("{}O", &row[1..]);
+    let stats = analyze(&bounded(&content));
+
+    assert_eq!(stats.open_cells, 1600);
+    assert!(!stats.diameter_exact);
+    // Corner to corner in an open room is found by the sweeps anyway.
+    assert_eq!(stats.diameter, 78);
+}
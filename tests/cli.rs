@@ -0,0 +1,106 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn mazes(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mazes"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_solve_from_stdin() {
+    let output = mazes(&["solve", "--topology", "bounded"], "#####\n#i O#\n#####\n");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "#####\n#i.O#\n#####\n");
+
+    let output = mazes(&["solve", "-", "--json", "--topology", "bounded"], "i O\n");
+    let solution: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(solution["steps"], 2);
+    assert_eq!(solution["algorithm"], "bfs");
+
+    let output = mazes(&["solve", "--topology", "bounded"], "i#O\n");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no path found"));
+}
+
+#[test]
+fn test_generate_is_reproducible() {
+    let args = [
+        "generate",
+        "--rows",
+        "4",
+        "--cols",
+        "6",
+        "--seed",
+        "7",
+        "--algorithm",
+        "prim",
+    ];
+    let first = mazes(&args, "");
+    assert!(first.status.success());
+    assert_eq!(stdout(&first), stdout(&mazes(&args, "")));
+    assert_eq!(stdout(&first).lines().count(), 9);
+
+    // The generated maze is valid input for the other commands.
+    let validated = mazes(&["validate"], &stdout(&first));
+    assert_eq!(stdout(&validated), "-: ok\n");
+}
+
+#[test]
+fn test_render_and_validate() {
+    let output = mazes(
+        &["render", "--format", "unicode", "--topology", "bounded"],
+        "###\n#i#\n#O#\n",
+    );
+    assert_eq!(stdout(&output), "┌─┐\n│i│\n╵O╵\n");
+
+    let dir = tempfile::tempdir().unwrap();
+    let good = dir.path().join("good.txt");
+    let bad = dir.path().join("bad.txt");
+    std::fs::write(&good, "iO\n").unwrap();
+    std::fs::write(&bad, "i\n").unwrap();
+
+    let output = mazes(
+        &["validate", good.to_str().unwrap(), bad.to_str().unwrap()],
+        "",
+    );
+    assert!(!output.status.success());
+    let report = stdout(&output);
+    assert!(report.contains("good.txt: ok"));
+    assert!(report.contains("bad.txt: End position 'O' not found"));
+}
+
+#[test]
+fn test_stats() {
+    let output = mazes(&["stats", "--json", "--topology", "bounded"], "i  \n# O\n");
+    let stats: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(stats["walls"], 1);
+    assert_eq!(stats["non_wall_cells"], 5);
+    assert_eq!(stats["open_cells"], 5);
+    assert_eq!(stats["shortest_path"], 3);
+    assert_eq!(stats["shortest_paths"], 2);
+}
+
+#[test]
+fn test_stats_open_cells_match_the_server() {
+    // The door is not a wall, but not open either until a key opens it.
+    let output = mazes(&["stats", "--json", "--topology", "bounded"], "i  \n#AO\n");
+    let stats: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(stats["non_wall_cells"], 5);
+    assert_eq!(stats["open_cells"], 4);
+}
@@ -0,0 +1,9 @@
+use mazes::{Map, Topology};
+
+// Parses a test maze without wrapping edges, so steps off the grid are
+// blocked.
+pub fn bounded(content: &str) -> Map {
+    let mut map = Map::parse_from_string(content).unwrap();
+    map.topology = Topology::Bounded;
+    map
+}
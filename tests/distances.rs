@@ -0,0 +1,32 @@
+mod common;
+
+use common::bounded;
+use mazes::domain::DistanceField;
+use mazes::{Map, Position};
+
+#[test]
+fn test_distance_field() {
+    let map = bounded("i  #\n## #\n#O  ");
+    let field = DistanceField::compute(&map, map.start);
+
+    assert_eq!(field.get(map.start), Some(0));
+    assert_eq!(field.get(Position { row: 0, col: 2 }), Some(2));
+    assert_eq!(field.get(map.end), Some(5));
+    // Walls have no distance.
+    assert_eq!(field.get(Position { row: 0, col: 3 }), None);
+    assert_eq!(field.get(Position { row: 9, col: 9 }), None);
+    // The end and the cell right of it tie; the first in reading order wins.
+    assert_eq!(field.farthest(), Some((map.end, 5)));
+}
+
+#[test]
+fn test_distance_field_unreachable_and_wrapping() {
+    let closed = bounded("i#O");
+    let field = DistanceField::compute(&closed, closed.start);
+    assert_eq!(field.get(closed.end), None);
+
+    // On a torus the end is one step to the left.
+    let torus = Map::parse_from_string("i#O").unwrap();
+    let field = DistanceField::compute(&torus, torus.start);
+    assert_eq!(field.get(torus.end), Some(1));
+}
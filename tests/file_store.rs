@@ -0,0 +1,168 @@
+use std::fs;
+use std::time::Duration;
+
+use mazes::db::{FileMazeRepository, MazeFilter, MazeStore};
+use mazes::Topology;
+
+#[tokio::test]
+async fn test_serves_existing_files() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("small.txt"), "#i#\n# #\n#O#\n").unwrap();
+    fs::write(dir.path().join("notes.md"), "not a maze").unwrap();
+
+    let store = FileMazeRepository::open(dir.path()).unwrap();
+    let all = store.list(&MazeFilter::default()).await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].name, "small");
+    assert_eq!(all[0].topology, Topology::Bounded);
+
+    // Without a sidecar the id comes from the file name.
+    let reopened = FileMazeRepository::open(dir.path()).unwrap();
+    let maze = reopened.get_by_id(all[0].id).await.unwrap().unwrap();
+    assert_eq!(maze.content, "#i#\n# #\n#O#");
+}
+
+#[tokio::test]
+async fn test_create_and_delete_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileMazeRepository::open(dir.path()).unwrap();
+
+    let maze = store.create("Created", "i O", Topology::Bounded).await.unwrap();
+    let text = dir.path().join(format!("{}.txt", maze.id));
+    let sidecar = dir.path().join(format!("{}.json", maze.id));
+    assert_eq!(fs::read_to_string(&text).unwrap(), "i O\n");
+    assert!(sidecar.exists());
+
+    let reopened = FileMazeRepository::open(dir.path()).unwrap();
+    let loaded = reopened.get_by_id(maze.id).await.unwrap().unwrap();
+    assert_eq!(loaded.name, "Created");
+    assert_eq!(loaded.topology, Topology::Bounded);
+    assert_eq!(loaded.created_at, maze.created_at);
+
+    assert!(store.delete(maze.id).await.unwrap());
+    assert!(!store.delete(maze.id).await.unwrap());
+    assert!(!text.exists());
+    assert!(!sidecar.exists());
+}
+
+#[tokio::test]
+async fn test_watch_picks_up_new_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileMazeRepository::open(dir.path()).unwrap().watch().unwrap();
+    assert!(store.list(&MazeFilter::default()).await.unwrap().is_empty());
+
+    fs::write(dir.path().join("added.txt"), "iO\n").unwrap();
+
+    for _ in 0..50 {
+        if !store.list(&MazeFilter::default()).await.unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let all = store.list(&MazeFilter::default()).await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].name, "added");
+}
+
+#[tokio::test]
+async fn test_revisions_survive_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("level.txt"), "iO\n").unwrap();
+    let store = FileMazeRepository::open(dir.path()).unwrap();
+    let maze = store.list(&MazeFilter::default()).await.unwrap().remove(0);
+
+    let updated = store
+        .update(maze.id, "Level one", "i O", Topology::Bounded)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.revision, 2);
+    assert_eq!(fs::read_to_string(dir.path().join("level.txt")).unwrap(), "i O\n");
+
+    let reopened = FileMazeRepository::open(dir.path()).unwrap();
+    let loaded = reopened.get_by_id(maze.id).await.unwrap().unwrap();
+    assert_eq!(loaded.name, "Level one");
+    assert_eq!(loaded.revision, 2);
+
+    let revisions = reopened.revisions(maze.id).await.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].name, "level");
+    assert_eq!(revisions[0].content, "iO");
+
+    assert!(reopened.delete(maze.id).await.unwrap());
+    assert!(!dir.path().join(".revisions").join("level").exists());
+}
+
+#[tokio::test]
+async fn test_bad_files_are_skipped() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("good.txt"), "iO\n").unwrap();
+    fs::write(dir.path().join("broken.txt"), "i O\n").unwrap();
+    fs::write(dir.path().join("broken.json"), "{ not json").unwrap();
+
+    let store = FileMazeRepository::open(dir.path()).unwrap();
+    let all = store.list(&MazeFilter::default()).await.unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].name, "good");
+
+    let errors = store.scan_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path, dir.path().join("broken.txt"));
+
+    // Fixing the file brings it back on the next reload.
+    fs::remove_file(dir.path().join("broken.json")).unwrap();
+    store.reload().unwrap();
+    assert_eq!(store.list(&MazeFilter::default()).await.unwrap().len(), 2);
+    assert!(store.scan_errors().is_empty());
+}
+
+#[tokio::test]
+async fn test_reload_keeps_analysis_of_unchanged_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileMazeRepository::open(dir.path()).unwrap();
+    let maze = store
+        .create("Forked", "#i#\n   \n#O#", Topology::Bounded)
+        .await
+        .unwrap();
+    let listed = |store: FileMazeRepository| async move {
+        store.list(&MazeFilter::default()).await.unwrap().remove(0)
+    };
+    assert_eq!(listed(store.clone()).await.dead_ends, Some(2));
+
+    // A renamed sidecar keeps the analysis.
+    let sidecar = dir.path().join(format!("{}.json", maze.id));
+    let json = fs::read_to_string(&sidecar).unwrap();
+    fs::write(&sidecar, json.replace("Forked", "Renamed")).unwrap();
+    store.reload().unwrap();
+    let summary = listed(store.clone()).await;
+    assert_eq!(summary.name, "Renamed");
+    assert_eq!(summary.dead_ends, Some(2));
+
+    // A rewritten maze file is analyzed again.
+    let text = dir.path().join(format!("{}.txt", maze.id));
+    fs::write(&text, "i  O\n").unwrap();
+    let later = fs::metadata(&text).unwrap().modified().unwrap() + Duration::from_secs(1);
+    fs::File::options()
+        .write(true)
+        .open(&text)
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+    store.reload().unwrap();
+    assert_eq!(listed(store).await.dead_ends, Some(0));
+}
+
+#[tokio::test]
+async fn test_checked_in_maps_topology() {
+    let store = FileMazeRepository::open("maps").unwrap();
+    let summaries = store.list(&MazeFilter::default()).await.unwrap();
+    let topology = |name: &str| {
+        summaries
+            .iter()
+            .find(|summary| summary.name == name)
+            .map(|summary| summary.topology)
+    };
+    assert_eq!(topology("simple"), Some(Topology::Bounded));
+    assert_eq!(topology("toroidal"), Some(Topology::Torus));
+    assert!(store.scan_errors().is_empty());
+}
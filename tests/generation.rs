@@ -0,0 +1,68 @@
+use mazes::domain::{generate, GenerationAlgorithm, GenerationOptions, Placement};
+use mazes::{Cell, find_path};
+
+const ALGORITHMS: [GenerationAlgorithm; 6] = [
+    GenerationAlgorithm::RecursiveBacktracker,
+    GenerationAlgorithm::Prim,
+    GenerationAlgorithm::Kruskal,
+    GenerationAlgorithm::Eller,
+    GenerationAlgorithm::Wilson,
+    GenerationAlgorithm::AldousBroder,
+];
+
+fn options(seed: u64, toroidal: bool) -> GenerationOptions {
+    GenerationOptions {
+        rows: 6,
+        cols: 9,
+        seed,
+        toroidal,
+        placement: Placement::Corners,
+    }
+}
+
+#[test]
+fn test_same_seed_same_maze() {
+    for algorithm in ALGORITHMS {
+        let first = generate(algorithm, &options(42, false)).unwrap();
+        let second = generate(algorithm, &options(42, false)).unwrap();
+        let other = generate(algorithm, &options(43, false)).unwrap();
+
+        assert_eq!(first.to_string(), second.to_string(), "{}", algorithm);
+        assert_ne!(first.to_string(), other.to_string(), "{}", algorithm);
+    }
+}
+
+#[test]
+fn test_generated_mazes_are_perfect() {
+    for toroidal in [false, true] {
+        for algorithm in ALGORITHMS {
+            let map = generate(algorithm, &options(7, toroidal)).unwrap();
+            let expected_rows = if toroidal { 12 } else { 13 };
+            assert_eq!(map.rows, expected_rows, "{}", algorithm);
+
+            // A spanning tree over 54 cells has 53 passages.
+            let open = map.grid.iter().flatten().filter(|&&cell| cell != Cell::Wall).count();
+            assert_eq!(open, 54 + 53, "{} toroidal={}", algorithm, toroidal);
+            assert!(find_path(&map).is_some(), "{} toroidal={}", algorithm, toroidal);
+        }
+    }
+}
+
+#[test]
+fn test_farthest_placement() {
+    let mut opts = options(3, false);
+    opts.placement = Placement::Farthest;
+    let farthest = generate(GenerationAlgorithm::Prim, &opts).unwrap();
+
+    opts.placement = Placement::Random;
+    let random = generate(GenerationAlgorithm::Prim, &opts).unwrap();
+
+    assert!(find_path(&farthest).unwrap().len() >= find_path(&random).unwrap().len());
+}
+
+#[test]
+fn test_rejects_empty_size() {
+    let mut opts = options(1, false);
+    opts.rows = 0;
+    assert!(generate(GenerationAlgorithm::Kruskal, &opts).is_err());
+}
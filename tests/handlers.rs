@@ -1,14 +1,16 @@
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
 use http_body_util::BodyExt;
-use mazes::{db::MazeRepository, handlers};
+use mazes::{db::InMemoryMazeRepository, handlers};
 use tower::ServiceExt;
 
 #[tokio::test]
 async fn test_create_maze() {
-    let app = handlers::create_router(MazeRepository::new());
+    let app = handlers::create_router(InMemoryMazeRepository::new());
 
     let request = Request::builder()
         .method("POST")
@@ -23,7 +25,7 @@ async fn test_create_maze() {
 
 #[tokio::test]
 async fn test_get_maze() {
-    let app = handlers::create_router(MazeRepository::new());
+    let app = handlers::create_router(InMemoryMazeRepository::new());
 
     let create_request = Request::builder()
         .method("POST")
@@ -50,7 +52,7 @@ async fn test_get_maze() {
 
 #[tokio::test]
 async fn test_get_all_mazes() {
-    let app = handlers::create_router(MazeRepository::new());
+    let app = handlers::create_router(InMemoryMazeRepository::new());
 
     let request = Request::builder()
         .method("GET")
@@ -64,7 +66,7 @@ async fn test_get_all_mazes() {
 
 #[tokio::test]
 async fn test_delete_maze() {
-    let app = handlers::create_router(MazeRepository::new());
+    let app = handlers::create_router(InMemoryMazeRepository::new());
 
     let create_request = Request::builder()
         .method("POST")
@@ -98,7 +100,7 @@ async fn test_delete_maze() {
 
 #[tokio::test]
 async fn test_get_maze_solution() {
-    let app = handlers::create_router(MazeRepository::new());
+    let app = handlers::create_router(InMemoryMazeRepository::new());
 
     let create_request = Request::builder()
         .method("POST")
@@ -123,3 +125,572 @@ async fn test_get_maze_solution() {
     let solution_response = app.oneshot(solution_request).await.unwrap();
     assert_eq!(solution_response.status(), StatusCode::OK);
 }
+
+#[tokio::test]
+async fn test_get_maze_solution_with_algorithm() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from("{\"name\":\"Test Maze\",\"content\":\"#####\\n#i O#\\n#####\"}"))
+        .unwrap();
+
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let solution_request = Request::builder()
+        .method("POST")
+        .uri(&format!("/api/mazes/{}/solution", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"player_row":1,"player_col":1,"portal_row":1,"portal_col":3,"algorithm":"wall_follower"}"#,
+        ))
+        .unwrap();
+
+    let solution_response = app.oneshot(solution_request).await.unwrap();
+    assert_eq!(solution_response.status(), StatusCode::OK);
+
+    let body = solution_response.into_body().collect().await.unwrap().to_bytes();
+    let solution: mazes::entities::MazeSolutionResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(solution.algorithm, mazes::Algorithm::WallFollower);
+}
+
+#[tokio::test]
+async fn test_generate_maze() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes/generate")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"algorithm":"kruskal","rows":4,"cols":5,"seed":99}"#,
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let generated: mazes::entities::GeneratedMazeResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(generated.generation.seed, 99);
+    assert_eq!(generated.maze.content.lines().count(), 9);
+    assert!(mazes::Map::parse_from_string(&generated.maze.content).is_ok());
+}
+
+#[tokio::test]
+async fn test_create_maze_with_topology() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"name":"Strip","content":"i  \n   \n  O","topology":"mobius"}"#,
+        ))
+        .unwrap();
+
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(maze.topology, mazes::Topology::Mobius);
+}
+
+#[tokio::test]
+async fn test_create_maze_with_validation() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create = |content: &str, validation: &str| {
+        Request::builder()
+            .method("POST")
+            .uri("/api/mazes")
+            .header("content-type", "application/json")
+            .body(Body::from(format!(
+                r#"{{"name":"Checked","content":"{}","topology":"bounded","validation":{}}}"#,
+                content, validation
+            )))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(create("i#O", r#"{"require_solvable":true}"#))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let error: mazes::entities::ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        error.message,
+        "maze failed validation: the end at (0, 2) cannot be reached from the start at (0, 0)"
+    );
+
+    // Without the check the same maze is stored.
+    let response = app.clone().oneshot(create("i#O", "{}")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(create(
+            "i  \\n   \\n  O",
+            r#"{"require_unique_solution":true,"max_rows":3}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_maze_solution_rejects_large_key_search() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let content = format!("iabcdefghjklmnp{}O", " ".repeat(84));
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            serde_json::json!({"name": "Keys", "content": content}).to_string(),
+        ))
+        .unwrap();
+
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let solution_request = Request::builder()
+        .method("POST")
+        .uri(&format!("/api/mazes/{}/solution", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"player_row":0,"player_col":0,"portal_row":0,"portal_col":99,"algorithm":"key_collector"}"#,
+        ))
+        .unwrap();
+
+    let solution_response = app.oneshot(solution_request).await.unwrap();
+    assert_eq!(solution_response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_maze_solution_structured_path() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from("{\"name\":\"Test Maze\",\"content\":\"#####\\n#i O#\\n#####\"}"))
+        .unwrap();
+
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let solution_request = Request::builder()
+        .method("POST")
+        .uri(&format!("/api/mazes/{}/solution", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"player_row":1,"player_col":1,"portal_row":1,"portal_col":3,"include_ascii":false}"#,
+        ))
+        .unwrap();
+
+    let solution_response = app.oneshot(solution_request).await.unwrap();
+    assert_eq!(solution_response.status(), StatusCode::OK);
+
+    let body = solution_response.into_body().collect().await.unwrap().to_bytes();
+    let solution: mazes::entities::MazeSolutionResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(solution.steps, 2);
+    assert_eq!(solution.path.len(), 3);
+    assert!(solution.moves.iter().all(|step| step.direction == "R" && !step.wrapped));
+    assert!(solution.explored >= 3);
+    assert!(solution.solution.is_none());
+}
+
+#[tokio::test]
+async fn test_list_mazes_paginated() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    for name in ["First", "Second", "Third"] {
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/mazes")
+            .header("content-type", "application/json")
+            .body(Body::from(format!(
+                "{{\"name\":\"{}\",\"content\":\"###\\n#iO\\n###\"}}",
+                name
+            )))
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap();
+    }
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/mazes?limit=2&sort=name")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: mazes::entities::MazePage = serde_json::from_slice(&body).unwrap();
+    let names: Vec<&str> = page.items.iter().map(|maze| maze.name.as_str()).collect();
+    assert_eq!(names, ["First", "Second"]);
+    assert_eq!((page.items[0].rows, page.items[0].cols), (3, 3));
+    let cursor = page.next_cursor.unwrap();
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/mazes?limit=2&sort=name&cursor={}", cursor))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: mazes::entities::MazePage = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].name, "Third");
+    assert!(page.next_cursor.is_none());
+
+    let request = Request::builder()
+        .method("GET")
+        .uri("/api/mazes?limit=0")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_update_and_rollback_maze() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from("{\"name\":\"Test Maze\",\"content\":\"###\\n#iO\\n###\"}"))
+        .unwrap();
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let put_request = Request::builder()
+        .method("PUT")
+        .uri(format!("/api/mazes/{}", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from("{\"name\":\"Wider\",\"content\":\"####\\n#i O\\n####\"}"))
+        .unwrap();
+    let put_response = app.clone().oneshot(put_request).await.unwrap();
+    assert_eq!(put_response.status(), StatusCode::OK);
+
+    let patch_request = Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/mazes/{}", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from("{\"name\":\"Renamed\"}"))
+        .unwrap();
+    let patch_response = app.clone().oneshot(patch_request).await.unwrap();
+    let body = patch_response.into_body().collect().await.unwrap().to_bytes();
+    let patched: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(patched.name, "Renamed");
+    assert_eq!(patched.content, "####\n#i O\n####");
+    assert_eq!(patched.revision, 3);
+
+    let invalid_request = Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/mazes/{}", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from("{\"content\":\"no start\"}"))
+        .unwrap();
+    let invalid_response = app.clone().oneshot(invalid_request).await.unwrap();
+    assert_eq!(invalid_response.status(), StatusCode::BAD_REQUEST);
+
+    let revisions_request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/mazes/{}/revisions", maze.id))
+        .body(Body::empty())
+        .unwrap();
+    let revisions_response = app.clone().oneshot(revisions_request).await.unwrap();
+    let body = revisions_response.into_body().collect().await.unwrap().to_bytes();
+    let revisions: Vec<mazes::entities::MazeRevision> = serde_json::from_slice(&body).unwrap();
+    let numbers: Vec<u32> = revisions.iter().map(|revision| revision.revision).collect();
+    assert_eq!(numbers, [2, 1]);
+
+    let rollback_request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/mazes/{}/revisions/1/rollback", maze.id))
+        .body(Body::empty())
+        .unwrap();
+    let rollback_response = app.clone().oneshot(rollback_request).await.unwrap();
+    let body = rollback_response.into_body().collect().await.unwrap().to_bytes();
+    let rolled_back: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(rolled_back.name, "Test Maze");
+    assert_eq!(rolled_back.content, "###\n#iO\n###");
+    assert_eq!(rolled_back.revision, 4);
+
+    let missing_request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/mazes/{}/revisions/9/rollback", maze.id))
+        .body(Body::empty())
+        .unwrap();
+    let missing_response = app.oneshot(missing_request).await.unwrap();
+    assert_eq!(missing_response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_maze_solution_cached() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from("{\"name\":\"Test Maze\",\"content\":\"#####\\n#i O#\\n#####\"}"))
+        .unwrap();
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let solve = |include_ascii: bool| {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/api/mazes/{}/solution", maze.id))
+            .header("content-type", "application/json")
+            .body(Body::from(format!(
+                r#"{{"player_row":1,"player_col":1,"portal_row":1,"portal_col":3,"include_ascii":{}}}"#,
+                include_ascii
+            )))
+            .unwrap()
+    };
+
+    let first = app.clone().oneshot(solve(true)).await.unwrap();
+    assert_eq!(first.headers()["x-cache"], "miss");
+
+    let second = app.clone().oneshot(solve(false)).await.unwrap();
+    assert_eq!(second.headers()["x-cache"], "hit");
+    let body = second.into_body().collect().await.unwrap().to_bytes();
+    let solution: mazes::entities::MazeSolutionResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(solution.steps, 2);
+    assert!(solution.solution.is_none());
+
+    // Editing the maze drops its cached solutions.
+    let patch_request = Request::builder()
+        .method("PATCH")
+        .uri(format!("/api/mazes/{}", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from("{\"content\":\"#####\\n#i#O#\\n#####\"}"))
+        .unwrap();
+    app.clone().oneshot(patch_request).await.unwrap();
+
+    // Unsolvable requests are cached too.
+    let third = app.clone().oneshot(solve(true)).await.unwrap();
+    assert_eq!(third.status(), StatusCode::BAD_REQUEST);
+    let fourth = app.clone().oneshot(solve(true)).await.unwrap();
+    assert_eq!(fourth.status(), StatusCode::BAD_REQUEST);
+
+    // Bad input is rejected every time, never cached.
+    let out_of_bounds = Request::builder()
+        .method("POST")
+        .uri(format!("/api/mazes/{}/solution", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"player_row":9,"player_col":1,"portal_row":1,"portal_col":3}"#,
+        ))
+        .unwrap();
+    let response = app.oneshot(out_of_bounds).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_maze_distances() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            "{\"name\":\"Test Maze\",\"content\":\"#####\\n#i O#\\n#####\",\"topology\":\"bounded\"}",
+        ))
+        .unwrap();
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let distances = |from: &str| {
+        Request::builder()
+            .uri(format!("/api/mazes/{}/distances?from={}", maze.id, from))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let first = app.clone().oneshot(distances("1,3")).await.unwrap();
+    assert_eq!(first.status(), StatusCode::OK);
+    assert_eq!(first.headers()["x-cache"], "miss");
+    let body = first.into_body().collect().await.unwrap().to_bytes();
+    let field: mazes::entities::MazeDistancesResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(field.revision, 1);
+    assert_eq!(field.max_distance, 2);
+    assert_eq!(field.distances[1], vec![None, Some(2), Some(1), Some(0), None]);
+    assert_eq!(field.distances[0], vec![None; 5]);
+
+    let second = app.clone().oneshot(distances("1,3")).await.unwrap();
+    assert_eq!(second.headers()["x-cache"], "hit");
+
+    for bad in ["0,0", "9,1", "1", "a,b"] {
+        let response = app.clone().oneshot(distances(bad)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "from={}", bad);
+    }
+}
+
+#[tokio::test]
+async fn test_render_maze_images() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from("{\"name\":\"Test Maze\",\"content\":\"#####\\n#i O#\\n#####\"}"))
+        .unwrap();
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let svg_request = Request::builder()
+        .uri(format!("/api/mazes/{}?format=svg&cell_size=8&wall=ff0000", maze.id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(svg_request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/svg+xml");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let svg = String::from_utf8(body.to_vec()).unwrap();
+    assert!(svg.contains("width=\"40\" height=\"24\""));
+    assert!(svg.contains("#ff0000"));
+
+    let png_request = Request::builder()
+        .uri(format!("/api/mazes/{}", maze.id))
+        .header("accept", "image/png")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(png_request).await.unwrap();
+    assert_eq!(response.headers()["content-type"], "image/png");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..4], b"\x89PNG");
+
+    let solution_request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/mazes/{}/solution?format=png", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"player_row":1,"player_col":1,"portal_row":1,"portal_col":3}"#))
+        .unwrap();
+    let response = app.clone().oneshot(solution_request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert_eq!(response.headers()["x-cache"], "miss");
+
+    let text_request = Request::builder()
+        .method("POST")
+        .uri(format!("/api/mazes/{}/solution?format=unicode&overlay=steps", maze.id))
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"player_row":1,"player_col":1,"portal_row":1,"portal_col":3}"#))
+        .unwrap();
+    let response = app.clone().oneshot(text_request).await.unwrap();
+    assert_eq!(response.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(response.headers()["x-cache"], "hit");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+        String::from_utf8(body.to_vec()).unwrap(),
+        "┌───┐\n│i1O│\n└───┘\n"
+    );
+
+    let too_large = Request::builder()
+        .uri(format!("/api/mazes/{}?format=png&cell_size=1000", maze.id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(too_large).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_render_heatmap_uses_stored_topology() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            r#"{"name":"Corridor","content":"i    O","topology":"bounded"}"#,
+        ))
+        .unwrap();
+    let response = app.clone().oneshot(create_request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let request = Request::builder()
+        .method("GET")
+        .uri(format!("/api/mazes/{}?format=ascii&overlay=heatmap", maze.id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Distances grow along the corridor instead of wrapping around its ends.
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(String::from_utf8(body.to_vec()).unwrap(), "i2457O\n");
+}
+
+#[tokio::test]
+async fn test_get_maze_stats() {
+    let app = handlers::create_router(InMemoryMazeRepository::new());
+
+    let create_request = Request::builder()
+        .method("POST")
+        .uri("/api/mazes")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            "{\"name\":\"Forked\",\"content\":\"#i#\\n   \\n#O#\",\"topology\":\"bounded\"}",
+        ))
+        .unwrap();
+    let create_response = app.clone().oneshot(create_request).await.unwrap();
+    let body = create_response.into_body().collect().await.unwrap().to_bytes();
+    let maze: mazes::entities::MazeResponse = serde_json::from_slice(&body).unwrap();
+
+    let stats_request = Request::builder()
+        .uri(format!("/api/mazes/{}/stats", maze.id))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(stats_request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stats["revision"], 1);
+    assert_eq!(stats["dead_ends"], 2);
+    assert_eq!(stats["junctions"], 1);
+    assert_eq!(stats["solution_length"], 2);
+    assert_eq!(stats["difficulty"], 46);
+
+    // The list filters and sorts on the stored figures.
+    let list_request = Request::builder()
+        .uri("/api/mazes?min_dead_ends=2&sort=difficulty")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(list_request).await.unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let page: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(page["items"][0]["difficulty"], 46);
+
+    let missing = Request::builder()
+        .uri(format!("/api/mazes/{}/stats", uuid::Uuid::new_v4()))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(missing).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
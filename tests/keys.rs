@@ -0,0 +1,78 @@
+mod common;
+
+use common::bounded;
+use mazes::domain::pathfinding::{check_keys, MAX_KEY_STATES};
+use mazes::domain::{solve_with_keys, Cell};
+use mazes::{find_path, Algorithm, Map};
+
+#[test]
+fn test_parse_keys_and_doors() {
+    let map = bounded("a i A O");
+    assert_eq!(map.grid[0][0], Cell::Key('a'));
+    assert_eq!(map.grid[0][4], Cell::Door('A'));
+    assert_eq!(map.to_string(), "a i A O\n");
+
+    // 'o' and 'I' would read as start and end; they are not keys or doors.
+    assert!(Map::parse_from_string("i o O").is_err());
+    assert!(Map::parse_from_string("i I O").is_err());
+}
+
+#[test]
+fn test_door_blocks_plain_solvers() {
+    let map = bounded("i A O");
+    assert!(find_path(&map).is_none());
+    assert!(solve_with_keys(&map).is_none());
+}
+
+#[test]
+fn test_key_opens_door() {
+    // The key sits behind the start, so the route has to double back.
+    let map = bounded("a iA O");
+
+    let (path, keys) = solve_with_keys(&map).unwrap();
+    assert_eq!(keys, vec!['a']);
+    assert_eq!(path.len(), 8);
+    assert_eq!(path.first(), Some(&map.start));
+    assert_eq!(path.last(), Some(&map.end));
+}
+
+#[test]
+fn test_keys_collected_in_order() {
+    // The b key lies behind door A, so a has to come first.
+    let map = bounded(concat!(
+        "#####\n",
+        "#a i#\n",
+        "###A#\n",
+        "#Bb #\n",
+        "#O###\n",
+        "#####",
+    ));
+
+    let (path, keys) = solve_with_keys(&map).unwrap();
+    assert_eq!(keys, vec!['a', 'b']);
+    assert_eq!(path.len(), 10);
+}
+
+#[test]
+fn test_key_collector_algorithm() {
+    let map = bounded("b iB O");
+    let result = Algorithm::KeyCollector.solver().search(&map);
+    assert_eq!(result.path.map(|path| path.len()), Some(8));
+    assert!(result.explored > 0);
+}
+
+#[test]
+fn test_key_search_is_bounded() {
+    // 100 open cells and 14 kinds of key make 1.6 million states.
+    let content = format!("iabcdefghjklmnp{}O", " ".repeat(84));
+    let map = bounded(&content);
+    let error = check_keys(&map).unwrap_err();
+    assert!(error.contains(&MAX_KEY_STATES.to_string()));
+
+    // Unchecked searches give up instead of running out of memory.
+    let result = Algorithm::KeyCollector.solver().search(&map);
+    assert_eq!(result.path, None);
+    assert_eq!(result.explored, 0);
+
+    assert!(check_keys(&bounded("b iB O")).is_ok());
+}
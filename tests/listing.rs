@@ -0,0 +1,146 @@
+use mazes::db::{Cursor, InMemoryMazeRepository, MazeFilter, MazeStore};
+use mazes::entities::{MazeListQuery, MazeSort, SortOrder};
+use mazes::Topology;
+
+async fn store() -> InMemoryMazeRepository {
+    let store = InMemoryMazeRepository::new();
+    for (name, content) in [
+        ("Tiny", "iO"),
+        ("Small corridor", "i  O"),
+        ("Square", "i  \n   \n  O"),
+        ("Big square", "i   \n    \n    \n   O"),
+    ] {
+        store.create(name, content, Topology::Bounded).await.unwrap();
+    }
+    store
+}
+
+#[tokio::test]
+async fn test_list_defaults_to_newest_first() {
+    let store = store().await;
+    let items = store.list(&MazeFilter::default()).await.unwrap();
+    let names: Vec<&str> = items.iter().map(|maze| maze.name.as_str()).collect();
+    assert_eq!(names, ["Big square", "Square", "Small corridor", "Tiny"]);
+    assert_eq!((items[0].rows, items[0].cols), (4, 4));
+}
+
+#[tokio::test]
+async fn test_list_filters() {
+    let store = store().await;
+
+    let filter = MazeFilter {
+        name: Some("SQUARE".to_string()),
+        ..MazeFilter::default()
+    };
+    assert_eq!(store.list(&filter).await.unwrap().len(), 2);
+
+    let filter = MazeFilter {
+        min_rows: Some(2),
+        max_cols: Some(3),
+        ..MazeFilter::default()
+    };
+    let items = store.list(&filter).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Square");
+}
+
+#[tokio::test]
+async fn test_list_cursor_pages() {
+    let store = store().await;
+    let mut filter = MazeFilter {
+        sort: MazeSort::Size,
+        order: SortOrder::Desc,
+        limit: 3,
+        ..MazeFilter::default()
+    };
+
+    let first = store.list(&filter).await.unwrap();
+    assert_eq!(first.len(), 3);
+    assert_eq!(first[0].name, "Big square");
+
+    filter.after = Some(Cursor::after(&first[2], MazeSort::Size));
+    let second = store.list(&filter).await.unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].name, "Tiny");
+}
+
+#[test]
+fn test_cursor_round_trip() {
+    let query = MazeListQuery {
+        sort: Some(MazeSort::Name),
+        cursor: Some("zz".to_string()),
+        ..MazeListQuery::default()
+    };
+    assert!(MazeFilter::try_from(query).is_err());
+
+    let cursor = Cursor {
+        key: mazes::db::listing::SortKey::Name("a\nname".to_string()),
+        id: uuid::Uuid::new_v4(),
+    };
+    let encoded = cursor.encode();
+    assert!(encoded.chars().all(|ch| ch.is_ascii_hexdigit()));
+    assert_eq!(Cursor::decode(&encoded, MazeSort::Name), Ok(cursor.clone()));
+    assert!(Cursor::decode(&encoded, MazeSort::Size).is_err());
+}
+
+#[test]
+fn test_query_validation() {
+    let query = MazeListQuery {
+        limit: Some(0),
+        ..MazeListQuery::default()
+    };
+    assert!(MazeFilter::try_from(query).is_err());
+
+    let query = MazeListQuery {
+        offset: Some(1),
+        cursor: Some(String::new()),
+        ..MazeListQuery::default()
+    };
+    assert!(MazeFilter::try_from(query).is_err());
+}
+
+#[tokio::test]
+async fn test_list_by_analysis() {
+    let store = InMemoryMazeRepository::new();
+    for (name, content) in [
+        ("Straight", "i     O"),
+        ("Winding", "i#   #\n # # #\n   # O"),
+        ("Forked", "#i#\n   \n#O#"),
+        ("Walled", "i#O"),
+    ] {
+        store.create(name, content, Topology::Bounded).await.unwrap();
+    }
+
+    // Unsolvable mazes have no difficulty and sort first.
+    let mut filter = MazeFilter {
+        sort: MazeSort::Difficulty,
+        order: SortOrder::Asc,
+        limit: 2,
+        ..MazeFilter::default()
+    };
+    let first = store.list(&filter).await.unwrap();
+    let names: Vec<&str> = first.iter().map(|maze| maze.name.as_str()).collect();
+    assert_eq!(names, ["Walled", "Straight"]);
+    assert_eq!(first[0].difficulty, None);
+    assert_eq!(first[0].dead_ends, Some(0));
+
+    filter.after = Some(Cursor::after(&first[1], MazeSort::Difficulty));
+    let second = store.list(&filter).await.unwrap();
+    let names: Vec<&str> = second.iter().map(|maze| maze.name.as_str()).collect();
+    assert_eq!(names, ["Winding", "Forked"]);
+
+    let filter = MazeFilter {
+        min_difficulty: Some(10),
+        ..MazeFilter::default()
+    };
+    assert_eq!(store.list(&filter).await.unwrap().len(), 2);
+
+    let filter = MazeFilter {
+        min_dead_ends: Some(1),
+        ..MazeFilter::default()
+    };
+    let items = store.list(&filter).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Forked");
+    assert_eq!(items[0].dead_ends, Some(2));
+}
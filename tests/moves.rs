@@ -0,0 +1,104 @@
+mod common;
+
+use mazes::{Algorithm, Map, MoveSet, Position, Topology, a_star, dijkstra, find_path};
+
+fn bounded(content: &str, moves: MoveSet) -> Map {
+    let mut map = common::bounded(content);
+    map.moves = moves;
+    map
+}
+
+#[test]
+fn test_diagonal_moves_shorten_paths() {
+    let orthogonal = bounded("i  \n   \n  O", MoveSet::Orthogonal);
+    let diagonal = bounded("i  \n   \n  O", MoveSet::Diagonal { corner_cutting: false });
+
+    assert_eq!(find_path(&orthogonal).unwrap().len(), 5);
+    assert_eq!(find_path(&diagonal).unwrap().len(), 3);
+}
+
+#[test]
+fn test_corner_cutting() {
+    let content = "i#\n#O";
+
+    let strict = bounded(content, MoveSet::Diagonal { corner_cutting: false });
+    assert!(find_path(&strict).is_none());
+
+    let cutting = bounded(content, MoveSet::Diagonal { corner_cutting: true });
+    assert_eq!(find_path(&cutting).unwrap().len(), 2);
+}
+
+#[test]
+fn test_knight_and_hex_moves() {
+    let knight = bounded("i##\n##O\n###", MoveSet::Knight);
+    assert_eq!(
+        find_path(&knight).unwrap(),
+        vec![Position { row: 0, col: 0 }, Position { row: 1, col: 2 }]
+    );
+
+    let hex = bounded("i# \n O#\n   ", MoveSet::Hex);
+    let neighbors = hex.get_neighbors(Position { row: 1, col: 1 });
+    assert!(neighbors.contains(&Position { row: 0, col: 2 }));
+    assert!(!neighbors.contains(&Position { row: 0, col: 0 }));
+}
+
+#[test]
+fn test_every_solver_respects_one_way_moves() {
+    // Only right and down are allowed, so the route has to go around.
+    let moves = MoveSet::Custom { offsets: vec![(0, 1), (1, 0)] };
+    let map = bounded("i  #\n## #\n   O", moves);
+
+    for algorithm in [
+        Algorithm::Bfs,
+        Algorithm::Dfs,
+        Algorithm::BidirectionalBfs,
+        Algorithm::AStar,
+        Algorithm::Dijkstra,
+        Algorithm::WallFollower,
+    ] {
+        let path = algorithm.solver().solve(&map).unwrap_or_else(|| panic!("{} found no path", algorithm));
+        for pair in path.windows(2) {
+            assert!(map.get_neighbors(pair[0]).contains(&pair[1]), "{}", algorithm);
+        }
+        assert_eq!(path.last(), Some(&map.end), "{}", algorithm);
+    }
+}
+
+#[test]
+fn test_bidirectional_search_across_flipped_seams() {
+    // The end lies one diagonal step from the start across the column seam,
+    // where the rows are flipped and a move is not undone by its opposite.
+    let custom = MoveSet::Custom { offsets: vec![(1, 1), (-1, -1), (0, 1), (0, -1)] };
+    let diagonal = MoveSet::Diagonal { corner_cutting: true };
+
+    for topology in [Topology::Mobius, Topology::Klein] {
+        for moves in [custom.clone(), diagonal.clone()] {
+            let mut map = bounded(" #  \nO  #\n   i", moves);
+            map.topology = topology;
+            let path = Algorithm::BidirectionalBfs.solver().solve(&map).unwrap();
+            let expected = Algorithm::Bfs.solver().solve(&map).unwrap();
+
+            assert_eq!(path.len(), expected.len(), "{}", topology);
+            assert!(mazes::domain::route::describe(&map, &path).is_some(), "{}", topology);
+        }
+    }
+}
+
+#[test]
+fn test_a_star_is_admissible_with_diagonals() {
+    let map = bounded(
+        "i   #   \n ## # # \n  #   # \n# ### ##\n      O ",
+        MoveSet::Diagonal { corner_cutting: false },
+    );
+    assert_eq!(a_star(&map).unwrap().1, dijkstra(&map).unwrap().1);
+}
+
+#[test]
+fn test_custom_moves_are_validated() {
+    assert!(MoveSet::Custom { offsets: vec![] }.validate().is_err());
+    assert!(MoveSet::Custom { offsets: vec![(0, 0)] }.validate().is_err());
+    assert!(MoveSet::Custom { offsets: vec![(3, 1)] }.validate().is_ok());
+    assert!(MoveSet::Custom { offsets: vec![(0, 100_000_001), (0, -1)] }.validate().is_err());
+    assert!(MoveSet::Custom { offsets: vec![(i32::MIN, 0)] }.validate().is_err());
+    assert!(MoveSet::Custom { offsets: vec![(16, -16)] }.validate().is_ok());
+}
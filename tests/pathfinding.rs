@@ -1,4 +1,6 @@
-use mazes::{Map, Position, Cell, find_path};
+use std::collections::HashMap;
+
+use mazes::{Algorithm, Map, MoveSet, Position, Cell, Topology, a_star, dijkstra, find_path};
 
 #[test]
 fn test_path_finding() {
@@ -12,6 +14,9 @@ fn test_path_finding() {
         end: Position { row: 2, col: 2 },
         rows: 3,
         cols: 3,
+        topology: Topology::Torus,
+        moves: MoveSet::Orthogonal,
+        portals: HashMap::new(),
     };
 
     let path = find_path(&map);
@@ -35,9 +40,66 @@ fn test_no_path() {
         end: Position { row: 1, col: 1 },
         rows: 3,
         cols: 3,
+        topology: Topology::Torus,
+        moves: MoveSet::Orthogonal,
+        portals: HashMap::new(),
     };
 
     let path = find_path(&map);
     assert!(path.is_some()); // Same start and end position
 }
 
+
+#[test]
+fn test_weighted_path_avoids_water() {
+    let map = Map::parse_from_string("#####\n#i~O#\n# = #\n#####").unwrap();
+
+    let (path, cost) = dijkstra(&map).unwrap();
+    assert_eq!(path.first(), Some(&map.start));
+    assert_eq!(path.last(), Some(&map.end));
+    assert_eq!(path.len(), 5);
+    assert_eq!(cost, 7);
+}
+
+#[test]
+fn test_a_star_matches_dijkstra_cost() {
+    let map = Map::parse_from_string("#i %  #\n# ## ~#\n#  =%O#\n#=== ##").unwrap();
+
+    let (_, dijkstra_cost) = dijkstra(&map).unwrap();
+    let (path, a_star_cost) = a_star(&map).unwrap();
+    assert_eq!(a_star_cost, dijkstra_cost);
+    assert_eq!(path.last(), Some(&map.end));
+}
+
+#[test]
+fn test_every_algorithm_reaches_the_end() {
+    let map = Map::parse_from_string(
+        "########\n#i     #\n# ######\n# #   ##\n# # # ##\n# # # ##\n#   #O##\n########",
+    )
+    .unwrap();
+
+    for algorithm in [
+        Algorithm::Bfs,
+        Algorithm::Dfs,
+        Algorithm::BidirectionalBfs,
+        Algorithm::AStar,
+        Algorithm::Dijkstra,
+        Algorithm::WallFollower,
+    ] {
+        let path = algorithm.solver().solve(&map).unwrap_or_else(|| panic!("{} found no path", algorithm));
+        assert_eq!(path.first(), Some(&map.start), "{}", algorithm);
+        assert_eq!(path.last(), Some(&map.end), "{}", algorithm);
+        for pair in path.windows(2) {
+            assert!(map.get_neighbors(pair[0]).contains(&pair[1]), "{}", algorithm);
+        }
+    }
+}
+
+#[test]
+fn test_bidirectional_bfs_is_shortest() {
+    let map = Map::parse_from_string("#######\n#i    #\n# ### #\n#     #\n# ### #\n#    O#\n#######").unwrap();
+
+    let bfs = Algorithm::Bfs.solver().solve(&map).unwrap();
+    let bidirectional = Algorithm::BidirectionalBfs.solver().solve(&map).unwrap();
+    assert_eq!(bidirectional.len(), bfs.len());
+}
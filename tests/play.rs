@@ -0,0 +1,53 @@
+mod common;
+
+use common::bounded;
+use mazes::domain::play::{Direction, Game};
+use mazes::{Map, Position};
+
+#[test]
+fn test_walls_block_and_the_end_wins() {
+    let mut game = Game::new(bounded("#####\n#i O#\n#####"), None);
+
+    assert!(!game.step(Direction::Up));
+    assert!(!game.step(Direction::Left));
+    assert!(game.step(Direction::Right));
+    assert!(game.step(Direction::Right));
+    assert!(game.is_won());
+    // Once the end is reached the player stays there.
+    assert!(!game.step(Direction::Left));
+
+    let report = game.report();
+    assert_eq!((report.moves, report.optimal), (2, Some(2)));
+    assert_eq!(report.to_string(), "Reached the end in 2 moves, a shortest route.");
+}
+
+#[test]
+fn test_wrapping_and_detours() {
+    // On a torus the end is one step left of the start.
+    let map = Map::parse_from_string("i  O").unwrap();
+    let mut game = Game::new(map, None);
+    assert!(game.step(Direction::Right));
+    assert!(game.step(Direction::Left));
+    assert!(game.step(Direction::Left));
+    assert_eq!(game.player(), Position { row: 0, col: 3 });
+
+    let report = game.report();
+    assert_eq!((report.moves, report.optimal), (3, Some(1)));
+    assert_eq!(
+        report.to_string(),
+        "Reached the end in 3 moves; the shortest route takes 1 (2 extra, 33% efficient)."
+    );
+}
+
+#[test]
+fn test_fog() {
+    let mut game = Game::new(bounded("i     O"), Some(1));
+    assert_eq!(game.view(), vec!["@ ░░░░░"]);
+
+    game.step(Direction::Right);
+    game.step(Direction::Right);
+    // Cells seen before stay visible, the walked ones marked.
+    assert_eq!(game.view(), vec!["..@ ░░░"]);
+    assert!(!game.is_visible(Position { row: 0, col: 0 }));
+    assert_eq!(game.report().to_string(), "Gave up after 2 moves.");
+}
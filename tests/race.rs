@@ -0,0 +1,277 @@
+use futures_util::{SinkExt, StreamExt};
+use mazes::{
+    db::{InMemoryMazeRepository, MazeStore},
+    domain::{Position, Topology},
+    entities::RaceServerMessage,
+    handlers,
+};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+// Serves a fresh router with one corridor maze and returns the race URL.
+async fn start_race_server() -> String {
+    let repository = InMemoryMazeRepository::new();
+    let maze = repository
+        .create("Corridor", "i  O", Topology::Bounded)
+        .await
+        .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let app = handlers::create_router(repository);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    format!("ws://{}/api/mazes/{}/race", address, maze.id)
+}
+
+async fn join(url: &str, name: &str) -> Client {
+    let (client, _) = connect_async(format!("{}?name={}", url, name))
+        .await
+        .unwrap();
+    client
+}
+
+async fn next(client: &mut Client) -> RaceServerMessage {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), client.next())
+            .await
+            .expect("timed out waiting for a message")
+            .unwrap()
+            .unwrap();
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+// Skips messages until one matches, as state updates arrive for every move.
+async fn wait_for(
+    client: &mut Client,
+    matches: impl Fn(&RaceServerMessage) -> bool,
+) -> RaceServerMessage {
+    loop {
+        let message = next(client).await;
+        if matches(&message) {
+            return message;
+        }
+    }
+}
+
+async fn send(client: &mut Client, text: &str) {
+    client.send(Message::Text(text.to_string())).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_race_welcome_and_state() {
+    let url = start_race_server().await;
+    let mut alice = join(&url, "alice").await;
+
+    match next(&mut alice).await {
+        RaceServerMessage::Welcome {
+            player,
+            session,
+            start,
+            end,
+            revision,
+            ..
+        } => {
+            assert_eq!(player, 1);
+            assert_eq!(session, "default");
+            assert_eq!(revision, 1);
+            assert_eq!(start, Position { row: 0, col: 0 });
+            assert_eq!(end, Position { row: 0, col: 3 });
+        }
+        other => panic!("expected a welcome, got {:?}", other),
+    }
+    match next(&mut alice).await {
+        RaceServerMessage::State { players } => {
+            assert_eq!(players.len(), 1);
+            assert_eq!(players[0].name, "alice");
+            assert_eq!(players[0].moves, 0);
+        }
+        other => panic!("expected the state, got {:?}", other),
+    }
+
+    let mut bob = join(&url, "bob").await;
+    assert!(matches!(
+        next(&mut bob).await,
+        RaceServerMessage::Welcome { player: 2, .. }
+    ));
+    let state = wait_for(&mut alice, |m| matches!(m, RaceServerMessage::State { .. })).await;
+    assert!(matches!(state, RaceServerMessage::State { players } if players.len() == 2));
+}
+
+#[tokio::test]
+async fn test_race_rejects_illegal_moves() {
+    let url = start_race_server().await;
+    let mut alice = join(&url, "alice").await;
+    next(&mut alice).await;
+
+    send(&mut alice, r#"{"type":"step","direction":"left"}"#).await;
+    let rejected = wait_for(&mut alice, |m| {
+        matches!(m, RaceServerMessage::Rejected { .. })
+    })
+    .await;
+    assert_eq!(
+        rejected,
+        RaceServerMessage::Rejected {
+            reason: "illegal move from (0, 0)".to_string()
+        }
+    );
+
+    send(&mut alice, r#"{"type":"move","to":{"row":0,"col":2}}"#).await;
+    let rejected = wait_for(&mut alice, |m| {
+        matches!(m, RaceServerMessage::Rejected { .. })
+    })
+    .await;
+    assert!(matches!(rejected, RaceServerMessage::Rejected { .. }));
+
+    send(&mut alice, "not json").await;
+    let rejected = wait_for(&mut alice, |m| {
+        matches!(m, RaceServerMessage::Rejected { .. })
+    })
+    .await;
+    assert!(
+        matches!(rejected, RaceServerMessage::Rejected { reason } if reason.starts_with("invalid message"))
+    );
+}
+
+#[tokio::test]
+async fn test_race_ranks_finishers() {
+    let url = start_race_server().await;
+    let mut alice = join(&url, "alice").await;
+    next(&mut alice).await;
+    let mut bob = join(&url, "bob").await;
+    next(&mut bob).await;
+
+    for _ in 0..3 {
+        send(&mut bob, r#"{"type":"step","direction":"right"}"#).await;
+    }
+    let state = wait_for(
+        &mut alice,
+        |m| matches!(m, RaceServerMessage::State { players } if players[0].rank.is_some()),
+    )
+    .await;
+    let RaceServerMessage::State { players } = state else {
+        unreachable!()
+    };
+    assert_eq!(players[0].name, "bob");
+    assert_eq!(players[0].rank, Some(1));
+    assert_eq!(players[0].moves, 3);
+    assert!(players[0].time_ms.is_some());
+    assert_eq!(players[1].rank, None);
+
+    send(&mut bob, r#"{"type":"step","direction":"left"}"#).await;
+    let rejected = wait_for(&mut bob, |m| {
+        matches!(m, RaceServerMessage::Rejected { .. })
+    })
+    .await;
+    assert_eq!(
+        rejected,
+        RaceServerMessage::Rejected {
+            reason: "already finished".to_string()
+        }
+    );
+
+    send(&mut alice, r#"{"type":"move","to":{"row":0,"col":1}}"#).await;
+    send(&mut alice, r#"{"type":"step","direction":"right"}"#).await;
+    send(&mut alice, r#"{"type":"step","direction":"right"}"#).await;
+    let finished = wait_for(&mut bob, |m| {
+        matches!(m, RaceServerMessage::Finished { .. })
+    })
+    .await;
+    let RaceServerMessage::Finished { ranking } = finished else {
+        unreachable!()
+    };
+    let names: Vec<&str> = ranking.iter().map(|player| player.name.as_str()).collect();
+    assert_eq!(names, ["bob", "alice"]);
+    assert_eq!(ranking[1].rank, Some(2));
+}
+
+#[tokio::test]
+async fn test_race_sessions_are_separate() {
+    let url = start_race_server().await;
+    let mut alice = join(&url, "alice").await;
+    next(&mut alice).await;
+
+    let (mut bob, _) = connect_async(format!("{}?name=bob&session=other", url))
+        .await
+        .unwrap();
+    match next(&mut bob).await {
+        RaceServerMessage::Welcome {
+            player, session, ..
+        } => {
+            assert_eq!(player, 1);
+            assert_eq!(session, "other");
+        }
+        other => panic!("expected a welcome, got {:?}", other),
+    }
+    let state = next(&mut bob).await;
+    assert!(matches!(state, RaceServerMessage::State { players } if players.len() == 1));
+}
+
+#[tokio::test]
+async fn test_race_requires_a_name_and_a_maze() {
+    let url = start_race_server().await;
+    assert!(connect_async(format!("{}?name=%20", url)).await.is_err());
+    let long = "s".repeat(65);
+    assert!(
+        connect_async(format!("{}?name=alice&session={}", url, long))
+            .await
+            .is_err()
+    );
+
+    let missing = url.rsplit_once("/api/").unwrap().0.to_string()
+        + "/api/mazes/00000000-0000-0000-0000-000000000000/race?name=alice";
+    assert!(connect_async(missing).await.is_err());
+}
+
+#[tokio::test]
+async fn test_race_ranks_after_a_finisher_leaves() {
+    let url = start_race_server().await;
+    let mut alice = join(&url, "alice").await;
+    next(&mut alice).await;
+    let mut bob = join(&url, "bob").await;
+    next(&mut bob).await;
+
+    for _ in 0..3 {
+        send(&mut bob, r#"{"type":"step","direction":"right"}"#).await;
+    }
+    wait_for(
+        &mut bob,
+        |m| matches!(m, RaceServerMessage::State { players } if players[0].rank.is_some()),
+    )
+    .await;
+    bob.close(None).await.unwrap();
+    wait_for(
+        &mut alice,
+        |m| matches!(m, RaceServerMessage::State { players } if players.len() == 1),
+    )
+    .await;
+
+    for _ in 0..3 {
+        send(&mut alice, r#"{"type":"step","direction":"right"}"#).await;
+    }
+    let finished = wait_for(&mut alice, |m| {
+        matches!(m, RaceServerMessage::Finished { .. })
+    })
+    .await;
+    let RaceServerMessage::Finished { ranking } = finished else {
+        unreachable!()
+    };
+    assert_eq!(ranking.len(), 1);
+    assert_eq!(ranking[0].rank, Some(1));
+}
+
+#[tokio::test]
+async fn test_race_limits_players_joining_together() {
+    let url = start_race_server().await;
+    let joins = (0..40).map(|i| connect_async(format!("{}?name=p{}", url, i)));
+    let results = futures_util::future::join_all(joins).await;
+
+    let joined = results.iter().filter(|result| result.is_ok()).count();
+    assert_eq!(joined, 32);
+}
@@ -0,0 +1,123 @@
+use mazes::domain::render::{cell_colors, render_png, render_svg, render_text, TextStyle};
+use mazes::domain::{Color, Overlay, RenderOptions};
+use mazes::{find_path, Map, Position, Topology};
+
+#[test]
+fn test_color_parsing() {
+    assert_eq!("#ff8000".parse::<Color>(), Ok(Color(255, 128, 0)));
+    assert_eq!("FF8000".parse::<Color>(), Ok(Color(255, 128, 0)));
+    assert!("#ff80".parse::<Color>().is_err());
+    assert!("#gg0000".parse::<Color>().is_err());
+    assert_eq!(Color(1, 2, 255).to_string(), "#0102ff");
+}
+
+#[test]
+fn test_path_gradient() {
+    let map = Map::parse_from_string("i   O").unwrap();
+    let path: Vec<Position> = (0..5).map(|col| Position { row: 0, col }).collect();
+    let options = RenderOptions {
+        path_start: Color(0, 0, 0),
+        path_end: Color(200, 100, 0),
+        ..RenderOptions::default()
+    };
+
+    let colors = cell_colors(&map, &path, &options);
+    // Start and end keep their own colours; the steps between fade.
+    assert_eq!(colors[0][0], options.start);
+    assert_eq!(colors[0][1], Color(50, 25, 0));
+    assert_eq!(colors[0][2], Color(100, 50, 0));
+    assert_eq!(colors[0][3], Color(150, 75, 0));
+    assert_eq!(colors[0][4], options.end);
+}
+
+#[test]
+fn test_render_png() {
+    let map = Map::parse_from_string("#i#\n#O#").unwrap();
+    let options = RenderOptions {
+        cell_size: 4,
+        ..RenderOptions::default()
+    };
+    let png = render_png(&map, &[], &options).unwrap();
+
+    let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!((info.width, info.height), (12, 8));
+
+    let pixel = |x: usize, y: usize| {
+        let i = (y * 12 + x) * 3;
+        Color(pixels[i], pixels[i + 1], pixels[i + 2])
+    };
+    assert_eq!(pixel(0, 0), options.wall);
+    assert_eq!(pixel(5, 2), options.start);
+    assert_eq!(pixel(7, 7), options.end);
+}
+
+#[test]
+fn test_render_svg() {
+    let map = Map::parse_from_string("i # O").unwrap();
+    let svg = render_svg(&map, &[], &RenderOptions::default());
+
+    assert!(svg.starts_with("<svg "));
+    assert!(svg.contains("width=\"80\" height=\"16\""));
+    // Background, start, wall and end.
+    assert_eq!(svg.matches("<rect").count(), 4);
+    assert!(svg.contains("x=\"32\" y=\"0\" width=\"16\" height=\"16\" fill=\"#222222\""));
+}
+
+fn walled() -> (Map, Vec<Position>) {
+    let mut map = Map::parse_from_string("#######\n#i    #\n# ### #\n#    O#\n#######").unwrap();
+    map.topology = Topology::Bounded;
+    let path = find_path(&map).unwrap();
+    (map, path)
+}
+
+fn text(map: &Map, path: &[Position], style: TextStyle, overlay: Overlay) -> String {
+    let options = RenderOptions {
+        overlay,
+        ..RenderOptions::default()
+    };
+    render_text(map, path, style, &options)
+}
+
+#[test]
+fn test_render_text_ascii() {
+    let (map, path) = walled();
+
+    // The ASCII path overlay matches `Display` with the path marked.
+    let mut marked = Map::parse_from_string(&map.to_string()).unwrap();
+    marked.mark_path(&path);
+    assert_eq!(text(&map, &path, TextStyle::Ascii, Overlay::Path), marked.to_string());
+
+    assert_eq!(
+        text(&map, &path, TextStyle::Ascii, Overlay::Steps),
+        "#######\n#i    #\n#1### #\n#2345O#\n#######\n"
+    );
+    assert_eq!(
+        text(&map, &path, TextStyle::Ascii, Overlay::Heatmap),
+        "#######\n#i2356#\n#2###8#\n#3568O#\n#######\n"
+    );
+}
+
+#[test]
+fn test_render_text_unicode() {
+    let (map, path) = walled();
+    assert_eq!(
+        text(&map, &path, TextStyle::Unicode, Overlay::Path),
+        "┌─────┐\n│i    │\n│·╶─╴ │\n│····O│\n└─────┘\n"
+    );
+
+    let ansi = text(&map, &path, TextStyle::Ansi, Overlay::Path);
+    assert!(ansi.contains("\x1b[1;38;2;46;125;50mi\x1b[0m"));
+    // Without the escapes it is the Unicode rendering.
+    let mut plain = String::new();
+    let mut chars = ansi.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            plain.push(ch);
+        }
+    }
+    assert_eq!(plain, text(&map, &path, TextStyle::Unicode, Overlay::Path));
+}
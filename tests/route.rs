@@ -0,0 +1,64 @@
+use mazes::domain::route::describe;
+use mazes::{Algorithm, Map, MoveSet, Position, Topology, find_path};
+
+#[test]
+fn test_describe_marks_wrapped_moves() {
+    let mut map = Map::parse_from_string("# #\n#i#\n# #\n#O#\n# #").unwrap();
+    map.end = Position { row: 4, col: 1 };
+    map.start = Position { row: 0, col: 1 };
+
+    let path = find_path(&map).unwrap();
+    let steps = describe(&map, &path).unwrap();
+
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0].direction, "U");
+    assert!(steps[0].wrapped);
+
+    map.topology = Topology::Bounded;
+    let path = find_path(&map).unwrap();
+    let steps = describe(&map, &path).unwrap();
+    assert_eq!(steps.len(), 4);
+    assert!(steps.iter().all(|step| step.direction == "D" && !step.wrapped));
+}
+
+#[test]
+fn test_describe_knight_moves() {
+    let mut map = Map::parse_from_string("i##\n##O\n###").unwrap();
+    map.topology = Topology::Bounded;
+    map.moves = MoveSet::Knight;
+
+    let steps = describe(&map, &find_path(&map).unwrap()).unwrap();
+    assert_eq!(steps[0].direction, "DRR");
+    assert_eq!((steps[0].dr, steps[0].dc), (1, 2));
+}
+
+#[test]
+fn test_describe_long_jumps_with_counts() {
+    let mut map = Map::parse_from_string("iO ").unwrap();
+    map.moves = MoveSet::Custom {
+        offsets: vec![(0, 13), (0, -1)],
+    };
+
+    let steps = describe(&map, &find_path(&map).unwrap()).unwrap();
+    assert_eq!(steps[0].direction, "R13");
+    assert_eq!((steps[0].dr, steps[0].dc), (0, 13));
+    assert!(steps[0].wrapped);
+}
+
+#[test]
+fn test_describe_rejects_disconnected_paths() {
+    let map = Map::parse_from_string("i  O").unwrap();
+    let path = [Position { row: 0, col: 0 }, Position { row: 0, col: 2 }];
+    assert!(describe(&map, &path).is_none());
+}
+
+#[test]
+fn test_search_reports_explored_cells() {
+    let map = Map::parse_from_string("#######\n#i    #\n# ### #\n#    O#\n#######").unwrap();
+
+    let bfs = Algorithm::Bfs.solver().search(&map);
+    let a_star = Algorithm::AStar.solver().search(&map);
+    assert!(bfs.path.is_some());
+    assert!(bfs.explored >= bfs.path.unwrap().len());
+    assert!(a_star.explored > 0);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::bounded;
+use mazes::domain::{count_shortest_paths, enumerate_shortest_paths, find_nearest_goal};
+use mazes::{Map, Position};
+
+#[test]
+fn test_nearest_goal() {
+    let map = bounded("#########\n#   i  O#\n#########");
+    let near = Position { row: 1, col: 6 };
+    let far = Position { row: 1, col: 1 };
+
+    let path = find_nearest_goal(&map, &[far, near]).unwrap();
+    assert_eq!(path.last(), Some(&near));
+    assert_eq!(path.len(), 3);
+    assert!(find_nearest_goal(&map, &[]).is_none());
+}
+
+#[test]
+fn test_count_shortest_paths() {
+    // Two rows of three cells: three ways to go right twice and down once.
+    let open = bounded("i  \n  O");
+    assert_eq!(count_shortest_paths(&open), 3);
+
+    let corridor = bounded("i  O");
+    assert_eq!(count_shortest_paths(&corridor), 1);
+
+    let blocked = bounded("i#O");
+    assert_eq!(count_shortest_paths(&blocked), 0);
+}
+
+#[test]
+fn test_enumerate_shortest_paths() {
+    let map = bounded("i  \n  O");
+
+    let all = enumerate_shortest_paths(&map, 10);
+    assert_eq!(all.len(), 3);
+    for path in &all {
+        assert_eq!(path.len(), 4);
+        assert_eq!(path.first(), Some(&map.start));
+        assert_eq!(path.last(), Some(&map.end));
+    }
+    assert_ne!(all[0], all[1]);
+
+    assert_eq!(enumerate_shortest_paths(&map, 2).len(), 2);
+}
+
+#[test]
+fn test_wrapping_duplicates_are_not_counted_twice() {
+    // On a two-column torus left and right lead to the same cell.
+    let map = Map::parse_from_string("iO").unwrap();
+    assert_eq!(count_shortest_paths(&map), 1);
+}
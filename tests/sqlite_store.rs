@@ -0,0 +1,260 @@
+#![cfg(feature = "sqlite")]
+
+use mazes::db::{listing, Cursor, MazeFilter, MazeStore, SqliteMazeRepository};
+use mazes::entities::MazeSort;
+use mazes::Topology;
+use sqlx::sqlite::SqlitePoolOptions;
+
+async fn store() -> SqliteMazeRepository {
+    // Every connection to `sqlite::memory:` is its own database, so the
+    // pool must not open a second one.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations/sqlite").run(&pool).await.unwrap();
+    SqliteMazeRepository::new(pool)
+}
+
+#[tokio::test]
+async fn test_sqlite_round_trip() {
+    let store = store().await;
+
+    let first = store.create("First", "iO", Topology::Bounded).await.unwrap();
+    let second = store.create("Second", "i O", Topology::Klein).await.unwrap();
+
+    let fetched = store.get_by_id(first.id).await.unwrap().unwrap();
+    assert_eq!(fetched.name, "First");
+    assert_eq!(fetched.content, "iO");
+    assert_eq!(fetched.topology, Topology::Bounded);
+    assert_eq!(fetched.created_at, first.created_at);
+
+    let all = store.list(&MazeFilter::default()).await.unwrap();
+    assert_eq!(all.len(), 2);
+    assert_eq!(all[0].id, second.id);
+
+    assert!(store.delete(first.id).await.unwrap());
+    assert!(!store.delete(first.id).await.unwrap());
+    assert!(store.get_by_id(first.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_sqlite_list() {
+    let store = store().await;
+    for (name, content) in [("Tiny", "iO"), ("Wide_one", "i    O"), ("Tall", "i\n \n \nO")] {
+        store.create(name, content, Topology::Bounded).await.unwrap();
+    }
+
+    let filter = MazeFilter {
+        name: Some("e_o".to_string()),
+        ..MazeFilter::default()
+    };
+    let items = store.list(&filter).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!((items[0].rows, items[0].cols), (1, 6));
+
+    // `_` is matched literally, not as a wildcard.
+    let filter = MazeFilter {
+        name: Some("e_".to_string()),
+        min_rows: Some(2),
+        ..MazeFilter::default()
+    };
+    assert!(store.list(&filter).await.unwrap().is_empty());
+
+    let mut filter = MazeFilter {
+        sort: MazeSort::Size,
+        order: mazes::entities::SortOrder::Asc,
+        limit: 2,
+        ..MazeFilter::default()
+    };
+    let first = store.list(&filter).await.unwrap();
+    let names: Vec<&str> = first.iter().map(|maze| maze.name.as_str()).collect();
+    assert_eq!(names, ["Tiny", "Tall"]);
+
+    filter.after = Some(Cursor::after(&first[1], MazeSort::Size));
+    let second = store.list(&filter).await.unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].name, "Wide_one");
+
+    let filter = MazeFilter {
+        after: Some(Cursor::after(&first[0], MazeSort::CreatedAt)),
+        ..MazeFilter::default()
+    };
+    assert!(store.list(&filter).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_sqlite_list_by_analysis() {
+    let store = store().await;
+    for (name, content) in [
+        ("Winding", "i#   #\n # # #\n   # O"),
+        ("Forked", "#i#\n   \n#O#"),
+        ("Walled", "i#O"),
+    ] {
+        store.create(name, content, Topology::Bounded).await.unwrap();
+    }
+
+    let mut filter = MazeFilter {
+        sort: MazeSort::Difficulty,
+        order: mazes::entities::SortOrder::Desc,
+        limit: 2,
+        ..MazeFilter::default()
+    };
+    let first = store.list(&filter).await.unwrap();
+    let names: Vec<&str> = first.iter().map(|maze| maze.name.as_str()).collect();
+    assert_eq!(names, ["Forked", "Winding"]);
+    assert_eq!(first[0].dead_ends, Some(2));
+
+    filter.after = Some(Cursor::after(&first[1], MazeSort::Difficulty));
+    let second = store.list(&filter).await.unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(second[0].difficulty, None);
+
+    let filter = MazeFilter {
+        max_difficulty: Some(30),
+        max_dead_ends: Some(0),
+        ..MazeFilter::default()
+    };
+    let items = store.list(&filter).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Winding");
+
+    // Editing re-analyzes the maze.
+    let walled = &second[0];
+    store
+        .update(walled.id, "Walled", "i O", Topology::Bounded)
+        .await
+        .unwrap();
+    assert_eq!(store.list(&filter).await.unwrap().len(), 2);
+    assert_eq!(store.backfill_analysis().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_sqlite_backfill_analysis() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations/sqlite").run(&pool).await.unwrap();
+    // Rows as they were before the analysis columns, more than one batch.
+    let straight = (0..listing::BACKFILL_BATCH).map(|_| ("Straight", "iO"));
+    for (name, content) in [("Forked", "#i#\n   \n#O#"), ("Broken", "no start")]
+        .into_iter()
+        .chain(straight)
+    {
+        sqlx::query(
+            "INSERT INTO mazes (id, name, content, topology, created_at, updated_at) \
+             VALUES ($1, $2, $3, 'bounded', $4, $4)",
+        )
+        .bind(uuid::Uuid::new_v4())
+        .bind(name)
+        .bind(content)
+        .bind(chrono::Utc::now())
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    sqlx::query("UPDATE mazes SET analyzed = FALSE")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let store = SqliteMazeRepository::new(pool.clone());
+    let filter = MazeFilter {
+        min_dead_ends: Some(1),
+        ..MazeFilter::default()
+    };
+    assert!(store.list(&filter).await.unwrap().is_empty());
+
+    let parsed = listing::BACKFILL_BATCH as usize + 1;
+    assert_eq!(store.backfill_analysis().await.unwrap(), parsed);
+    let items = store.list(&filter).await.unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Forked");
+    // Content that does not parse is marked too, so it is not retried.
+    let (pending,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM mazes WHERE NOT analyzed")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(pending, 0);
+    assert_eq!(store.backfill_analysis().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_sqlite_revisions() {
+    let store = store().await;
+    let maze = store.create("Draft", "iO", Topology::Torus).await.unwrap();
+
+    store.update(maze.id, "Second", "i O", Topology::Torus).await.unwrap();
+    let third = store
+        .update(maze.id, "Third", "i  O", Topology::Bounded)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(third.revision, 3);
+    assert_eq!(third.content, "i  O");
+
+    let revisions = store.revisions(maze.id).await.unwrap();
+    let numbers: Vec<u32> = revisions.iter().map(|revision| revision.revision).collect();
+    assert_eq!(numbers, [2, 1]);
+    assert_eq!(revisions[1].created_at, maze.updated_at);
+
+    let second = store.get_revision(maze.id, 2).await.unwrap().unwrap();
+    assert_eq!(second.name, "Second");
+
+    assert!(store.delete(maze.id).await.unwrap());
+    assert!(store.revisions(maze.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_sqlite_solution_cache() {
+    use mazes::db::{CachedSolution, SolutionKey};
+    use mazes::{Algorithm, Position};
+
+    let store = store().await;
+    let maze = store.create("Cached", "i O", Topology::Bounded).await.unwrap();
+    let key = SolutionKey {
+        maze_id: maze.id,
+        revision: maze.revision,
+        start: Position { row: 0, col: 0 },
+        end: Position { row: 0, col: 2 },
+        algorithm: Algorithm::Bfs,
+        options: "{\"kind\":\"orthogonal\"}".to_string(),
+    };
+
+    assert!(store.cached_solution(&key).await.unwrap().is_none());
+    store.cache_solution(&key, &CachedSolution::Unsolvable).await.unwrap();
+    assert!(matches!(
+        store.cached_solution(&key).await.unwrap(),
+        Some(CachedSolution::Unsolvable)
+    ));
+
+    store.update(maze.id, "Cached", "iO ", Topology::Bounded).await.unwrap();
+    assert!(store.cached_solution(&key).await.unwrap().is_none());
+
+    // Entries for an outdated revision are not stored at all.
+    store.cache_solution(&key, &CachedSolution::Unsolvable).await.unwrap();
+    assert!(store.cached_solution(&key).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_sqlite_distance_cache() {
+    use mazes::domain::DistanceField;
+    use mazes::{Map, Position};
+
+    let store = store().await;
+    let maze = store.create("Distances", "i O", Topology::Bounded).await.unwrap();
+    let from = Position { row: 0, col: 0 };
+    let field = DistanceField::compute(&Map::parse_from_string("i O").unwrap(), from);
+
+    assert!(store.cached_distances(maze.id, 1, from).await.unwrap().is_none());
+    store.cache_distances(maze.id, 1, &field).await.unwrap();
+    assert_eq!(store.cached_distances(maze.id, 1, from).await.unwrap(), Some(field.clone()));
+
+    store.update(maze.id, "Distances", "iO ", Topology::Bounded).await.unwrap();
+    assert!(store.cached_distances(maze.id, 1, from).await.unwrap().is_none());
+    store.cache_distances(maze.id, 1, &field).await.unwrap();
+    assert!(store.cached_distances(maze.id, 1, from).await.unwrap().is_none());
+}
@@ -0,0 +1,63 @@
+use mazes::db::{InMemoryMazeRepository, MazeFilter, MazeStore};
+use mazes::Topology;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_in_memory_round_trip() {
+    let store = InMemoryMazeRepository::new();
+
+    let first = store.create("First", "iO", Topology::Bounded).await.unwrap();
+    let second = store.create("Second", "i O", Topology::Torus).await.unwrap();
+
+    let fetched = store.get_by_id(first.id).await.unwrap().unwrap();
+    assert_eq!(fetched.name, "First");
+    assert_eq!(fetched.topology, Topology::Bounded);
+
+    let all = store.list(&MazeFilter::default()).await.unwrap();
+    assert_eq!(all.len(), 2);
+    let listed = all.iter().find(|maze| maze.id == second.id).unwrap();
+    assert_eq!(listed.name, "Second");
+    assert_eq!(listed.topology, Topology::Torus);
+
+    assert!(store.delete(first.id).await.unwrap());
+    assert!(!store.delete(first.id).await.unwrap());
+    assert!(store.get_by_id(first.id).await.unwrap().is_none());
+    assert!(store.get_by_id(Uuid::new_v4()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_in_memory_clones_share_state() {
+    let store = InMemoryMazeRepository::new();
+    let clone = store.clone();
+
+    let maze = store.create("Shared", "iO", Topology::Torus).await.unwrap();
+    assert!(clone.get_by_id(maze.id).await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_in_memory_revisions() {
+    let store = InMemoryMazeRepository::new();
+    let maze = store.create("Draft", "iO", Topology::Torus).await.unwrap();
+    assert_eq!(maze.revision, 1);
+
+    let updated = store
+        .update(maze.id, "Final", "i O", Topology::Bounded)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(updated.revision, 2);
+    assert_eq!(updated.created_at, maze.created_at);
+
+    let revisions = store.revisions(maze.id).await.unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].revision, 1);
+    assert_eq!(revisions[0].name, "Draft");
+    assert_eq!(revisions[0].content, "iO");
+
+    let first = store.get_revision(maze.id, 1).await.unwrap().unwrap();
+    assert_eq!(first.topology, Topology::Torus);
+    assert!(store.get_revision(maze.id, 2).await.unwrap().is_none());
+
+    let missing = store.update(Uuid::new_v4(), "x", "iO", Topology::Torus).await;
+    assert!(missing.unwrap().is_none());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::bounded;
+use mazes::domain::route::describe;
+use mazes::{find_path, Algorithm, Map, Position};
+
+// The right half is only reachable through the teleporter pair.
+const SPLIT: &str = "i 1#  \n###  #\n   #1O";
+
+#[test]
+fn test_parse_teleporters() {
+    let map = bounded(SPLIT);
+    let a = Position { row: 0, col: 2 };
+    let b = Position { row: 2, col: 4 };
+    assert_eq!(map.portals.get(&a), Some(&b));
+    assert_eq!(map.portals.get(&b), Some(&a));
+    assert_eq!(map.to_string(), format!("{}\n", SPLIT));
+}
+
+#[test]
+fn test_unpaired_teleporters_rejected() {
+    let err = Map::parse_from_string("i 1 O").err().unwrap();
+    assert!(err.contains("no partner"), "{}", err);
+
+    let err = Map::parse_from_string("i2 2 2O").err().unwrap();
+    assert!(err.contains("appears 3 times"), "{}", err);
+}
+
+#[test]
+fn test_teleport_is_instant() {
+    let map = bounded(SPLIT);
+    let path = find_path(&map).unwrap();
+    assert_eq!(
+        path,
+        vec![
+            Position { row: 0, col: 0 },
+            Position { row: 0, col: 1 },
+            Position { row: 2, col: 4 },
+            Position { row: 2, col: 5 },
+        ]
+    );
+}
+
+#[test]
+fn test_all_solvers_use_teleporters() {
+    // The wall follower is left out: a teleporter can drop it next to a
+    // different wall, so it is not guaranteed to reach the exit.
+    let map = bounded(SPLIT);
+    for algorithm in [
+        Algorithm::Bfs,
+        Algorithm::Dfs,
+        Algorithm::BidirectionalBfs,
+        Algorithm::AStar,
+        Algorithm::Dijkstra,
+        Algorithm::KeyCollector,
+    ] {
+        let path = algorithm.solver().solve(&map);
+        assert!(path.is_some(), "{} found no path", algorithm);
+    }
+}
+
+#[test]
+fn test_teleport_called_out_in_route() {
+    let map = bounded(SPLIT);
+    let path = find_path(&map).unwrap();
+    let steps = describe(&map, &path).unwrap();
+
+    assert_eq!(steps.len(), 3);
+    assert_eq!(steps[0].teleport, None);
+    assert_eq!(steps[1].teleport, Some(Position { row: 0, col: 2 }));
+    assert_eq!(steps[1].direction, "R");
+    assert_eq!(steps[2].teleport, None);
+}
@@ -0,0 +1,57 @@
+use mazes::{Map, Position, Topology, find_path};
+
+fn open_map(topology: Topology) -> Map {
+    let mut map = Map::parse_from_string("i  \n   \n  O").unwrap();
+    map.topology = topology;
+    map
+}
+
+#[test]
+fn test_bounded_has_no_wrap() {
+    let map = open_map(Topology::Bounded);
+
+    let neighbors = map.get_neighbors(Position { row: 0, col: 0 });
+    assert_eq!(neighbors.len(), 2);
+    assert_eq!(find_path(&map).unwrap().len(), 5);
+}
+
+#[test]
+fn test_cylinders_wrap_one_axis() {
+    let horizontal = open_map(Topology::Horizontal);
+    let neighbors = horizontal.get_neighbors(Position { row: 0, col: 0 });
+    assert!(neighbors.contains(&Position { row: 0, col: 2 }));
+    assert!(!neighbors.contains(&Position { row: 2, col: 0 }));
+
+    let vertical = open_map(Topology::Vertical);
+    let neighbors = vertical.get_neighbors(Position { row: 0, col: 0 });
+    assert!(neighbors.contains(&Position { row: 2, col: 0 }));
+    assert!(!neighbors.contains(&Position { row: 0, col: 2 }));
+}
+
+#[test]
+fn test_mobius_flips_rows_across_the_seam() {
+    let map = open_map(Topology::Mobius);
+
+    let neighbors = map.get_neighbors(Position { row: 0, col: 0 });
+    assert!(neighbors.contains(&Position { row: 2, col: 2 }));
+    assert!(!neighbors.contains(&Position { row: 2, col: 0 }));
+    assert_eq!(find_path(&map).unwrap().len(), 2);
+
+    let klein = open_map(Topology::Klein);
+    let neighbors = klein.get_neighbors(Position { row: 0, col: 0 });
+    assert!(neighbors.contains(&Position { row: 2, col: 2 }));
+    assert!(neighbors.contains(&Position { row: 2, col: 0 }));
+}
+
+#[test]
+fn test_simple_map_has_no_shortcuts_when_bounded() {
+    let mut map = Map::parse_from_string("##    #\n#  #i #\n#  O## \n   #   ").unwrap();
+    let torus_length = find_path(&map).unwrap().len();
+
+    map.topology = Topology::Bounded;
+    for pair in find_path(&map).unwrap().windows(2) {
+        let distance = pair[0].row.abs_diff(pair[1].row) + pair[0].col.abs_diff(pair[1].col);
+        assert_eq!(distance, 1);
+    }
+    assert!(find_path(&map).unwrap().len() >= torus_length);
+}
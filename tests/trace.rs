@@ -0,0 +1,209 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use common::bounded;
+use http_body_util::BodyExt;
+use mazes::db::{InMemoryMazeRepository, MazeStore};
+use mazes::domain::pathfinding::{can_trace, trace, MAX_FRONTIER, MAX_TRACE_CELLS};
+use mazes::domain::TraceFrame;
+use mazes::entities::MazeTraceEvent;
+use mazes::{handlers, Algorithm, Map, Position, Topology};
+use std::ops::ControlFlow;
+use tower::ServiceExt;
+
+fn frames(map: &Map, algorithm: Algorithm) -> (Vec<TraceFrame>, mazes::domain::SearchResult) {
+    let mut frames = Vec::new();
+    let result = trace(map, algorithm, |frame| {
+        frames.push(frame);
+        ControlFlow::Continue(())
+    })
+    .unwrap();
+    (frames, result.unwrap())
+}
+
+fn keep_going(_: TraceFrame) -> ControlFlow<()> {
+    ControlFlow::Continue(())
+}
+
+#[test]
+fn test_trace_matches_the_solver() {
+    let map = bounded("i  #\n## #\n#O  ");
+    for algorithm in [
+        Algorithm::Bfs,
+        Algorithm::Dfs,
+        Algorithm::AStar,
+        Algorithm::Dijkstra,
+    ] {
+        let (frames, result) = frames(&map, algorithm);
+        assert_eq!(result, algorithm.solver().search(&map), "{}", algorithm);
+        assert_eq!(frames.len(), result.explored, "{}", algorithm);
+        assert_eq!(frames[0].current, map.start);
+        assert_eq!(frames.last().unwrap().current, map.end);
+        let steps: Vec<usize> = frames.iter().map(|frame| frame.step).collect();
+        assert_eq!(steps, (1..=frames.len()).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn test_trace_bfs_frames() {
+    let map = bounded("i  \n # \n  O");
+    let (frames, _) = frames(&map, Algorithm::Bfs);
+
+    let right = Position { row: 0, col: 1 };
+    let down = Position { row: 1, col: 0 };
+    assert_eq!(frames[0].discovered, [down, right]);
+    assert_eq!(frames[0].frontier, [down, right]);
+    assert_eq!(frames[0].seen, 3);
+    // The queue is first in, first out.
+    assert_eq!(frames[1].current, down);
+    assert_eq!(frames[1].frontier[0], right);
+    // Every open cell is reached before the end comes off the queue.
+    assert_eq!(frames.last().unwrap().seen, 8);
+}
+
+#[test]
+fn test_trace_dfs_frontier_skips_stale_entries() {
+    let map = bounded("i  \n   \n  O");
+    let (frames, _) = frames(&map, Algorithm::Dfs);
+
+    for frame in &frames {
+        let mut unique = frame.frontier.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), frame.frontier.len());
+        let expanded: Vec<Position> = frames[..frame.step].iter().map(|f| f.current).collect();
+        assert!(frame.frontier.iter().all(|pos| !expanded.contains(pos)));
+    }
+    // The top of the stack goes next.
+    for pair in frames.windows(2) {
+        assert_eq!(pair[1].current, pair[0].frontier[0]);
+    }
+}
+
+#[test]
+fn test_trace_a_star_explores_less() {
+    // Dijkstra spreads both ways along the corridor; A* heads for the end.
+    let map = bounded("     i   O\n          ");
+    let (a_star, _) = frames(&map, Algorithm::AStar);
+    let (dijkstra, _) = frames(&map, Algorithm::Dijkstra);
+    assert!(a_star.len() < dijkstra.len());
+
+    // The heap is listed cheapest first.
+    for pair in a_star.windows(2) {
+        assert_eq!(pair[1].current, pair[0].frontier[0]);
+    }
+}
+
+#[test]
+fn test_trace_unsupported_and_unsolvable() {
+    assert!(!can_trace(Algorithm::WallFollower));
+    let map = bounded("i#O");
+    assert!(trace(&map, Algorithm::BidirectionalBfs, keep_going).is_err());
+
+    let (frames, result) = frames(&map, Algorithm::Bfs);
+    assert_eq!(result.path, None);
+    assert_eq!(frames.len(), 1);
+    assert!(frames[0].frontier.is_empty());
+}
+
+#[test]
+fn test_trace_stops_when_asked() {
+    let map = bounded("i    \n     \n    O");
+    for algorithm in [
+        Algorithm::Bfs,
+        Algorithm::Dfs,
+        Algorithm::AStar,
+        Algorithm::Dijkstra,
+    ] {
+        let mut steps = 0;
+        let result = trace(&map, algorithm, |_| {
+            steps += 1;
+            ControlFlow::Break(())
+        });
+        assert_eq!(result, Ok(None), "{}", algorithm);
+        assert_eq!(steps, 1, "{}", algorithm);
+    }
+}
+
+#[test]
+fn test_trace_limits_frontier_and_size() {
+    let open = vec![" ".repeat(40); 40].join("\n");
+    let map = bounded(&format!("i{}O", &open[1..open.len() - 1]));
+    for algorithm in [Algorithm::Bfs, Algorithm::Dfs, Algorithm::Dijkstra] {
+        let (frames, _) = frames(&map, algorithm);
+        assert!(frames
+            .iter()
+            .all(|frame| frame.frontier.len() <= MAX_FRONTIER));
+        assert!(frames
+            .iter()
+            .any(|frame| frame.frontier.len() == MAX_FRONTIER));
+    }
+
+    let side = (MAX_TRACE_CELLS as f64).sqrt() as usize + 1;
+    let large = vec![" ".repeat(side); side].join("\n");
+    let map = bounded(&format!("i{}O", &large[1..large.len() - 1]));
+    assert!(trace(&map, Algorithm::Bfs, keep_going).is_err());
+}
+
+#[tokio::test]
+async fn test_trace_endpoint() {
+    let repository = InMemoryMazeRepository::new();
+    let maze = repository
+        .create("Trace", "i  \n # \n  O", Topology::Bounded)
+        .await
+        .unwrap();
+    let app = handlers::create_router(repository);
+
+    let request = |algorithm: &str, accept: &str| {
+        Request::builder()
+            .method("POST")
+            .uri(format!("/api/mazes/{}/solution/trace", maze.id))
+            .header("content-type", "application/json")
+            .header("accept", accept)
+            .body(Body::from(format!(
+                r#"{{"player_row":0,"player_col":0,"portal_row":2,"portal_col":2,"algorithm":"{}"}}"#,
+                algorithm
+            )))
+            .unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(request("bfs", "application/x-ndjson"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let events: Vec<MazeTraceEvent> = std::str::from_utf8(&body)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let MazeTraceEvent::Done { path, explored } = events.last().unwrap() else {
+        panic!("the last event should be done");
+    };
+    assert_eq!(path.as_ref().unwrap().len(), 5);
+    assert_eq!(events.len(), explored + 1);
+    assert!(matches!(&events[0], MazeTraceEvent::Expand(frame) if frame.step == 1));
+
+    let response = app
+        .clone()
+        .oneshot(request("a_star", "text/event-stream"))
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = std::str::from_utf8(&body).unwrap();
+    assert!(body.starts_with("event: expand\ndata: {\"type\":\"expand\",\"step\":1,"));
+    assert!(body.contains("event: done\ndata: {\"type\":\"done\","));
+
+    let response = app
+        .oneshot(request("wall_follower", "application/x-ndjson"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
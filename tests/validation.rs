@@ -0,0 +1,125 @@
+mod common;
+
+use common::bounded;
+use mazes::domain::{validate, ValidationPolicy};
+use mazes::Topology;
+
+fn policy() -> ValidationPolicy {
+    ValidationPolicy {
+        require_solvable: true,
+        forbid_isolated_regions: true,
+        require_unique_solution: true,
+        ..ValidationPolicy::default()
+    }
+}
+
+#[test]
+fn test_default_policy_accepts_anything() {
+    assert!(validate(&bounded("i#O"), &ValidationPolicy::default()).is_ok());
+}
+
+#[test]
+fn test_tree_maze_passes() {
+    assert!(validate(&bounded("#i#\n   \n#O#"), &policy()).is_ok());
+    assert!(validate(&bounded("i#   #\n # # #\n   # O"), &policy()).is_ok());
+}
+
+#[test]
+fn test_unsolvable() {
+    let err = validate(&bounded("i#O"), &policy()).unwrap_err();
+    assert_eq!(
+        err,
+        "the end at (0, 2) cannot be reached from the start at (0, 0); \
+         1 open region cannot be reached from the start: the cell at (0, 2)"
+    );
+
+    // A key on the way opens the door.
+    assert!(validate(&bounded("iaAO"), &policy()).is_ok());
+    let err = validate(&bounded("iAaO"), &policy()).unwrap_err();
+    assert!(
+        err.starts_with("the end at (0, 3) cannot be reached"),
+        "{}",
+        err
+    );
+    // The door does not cut the cells behind it off.
+    assert!(!err.contains("open region"), "{}", err);
+}
+
+#[test]
+fn test_isolated_regions() {
+    let only_regions = ValidationPolicy {
+        forbid_isolated_regions: true,
+        ..ValidationPolicy::default()
+    };
+    let err = validate(&bounded("iO#  \n#####\n  # #"), &only_regions).unwrap_err();
+    assert_eq!(
+        err,
+        "3 open regions cannot be reached from the start: \
+         2 cells from (0, 3), 2 cells from (2, 0), the cell at (2, 3)"
+    );
+
+    let scattered = bounded(&format!(
+        "iO{}\n{}\n{}",
+        "#".repeat(11),
+        "#".repeat(13),
+        " #".repeat(6) + " "
+    ));
+    let err = validate(&scattered, &only_regions).unwrap_err();
+    assert!(err.starts_with("7 open regions"), "{}", err);
+    assert!(err.ends_with(", 2 more"), "{}", err);
+}
+
+#[test]
+fn test_second_route() {
+    let err = validate(&bounded("i  \n # \n  O"), &policy()).unwrap_err();
+    assert!(
+        err.starts_with("more than one route leads from start to end; the step from (0, 0) to "),
+        "{}",
+        err
+    );
+
+    // A loop off to the side of the only route is fine.
+    assert!(validate(&bounded("i O#\n## #\n   #\n # #\n   #"), &policy()).is_ok());
+
+    // Wrapping joins the ends of the corridor into a second route.
+    let mut ring = bounded("i  O ");
+    assert!(validate(&ring, &policy()).is_ok());
+    ring.topology = Topology::Horizontal;
+    assert!(validate(&ring, &policy()).is_err());
+}
+
+#[test]
+fn test_size_limits_are_checked_first() {
+    let limits = ValidationPolicy {
+        max_rows: Some(2),
+        max_cols: Some(2),
+        ..policy()
+    };
+    let err = validate(&bounded("i#O\n   \n   "), &limits).unwrap_err();
+    assert_eq!(
+        err,
+        "3 rows, at most 2 allowed; 3 columns, at most 2 allowed"
+    );
+    assert!(validate(&bounded("iO"), &limits).is_ok());
+}
+
+#[test]
+fn test_searches_only_run_when_a_rule_needs_them() {
+    // Too many kinds of key to search, which only matters to the rules
+    // that need a solution.
+    let keys = bounded(&format!("iabcdefghjklmnp{}O", " ".repeat(84)));
+    assert!(validate(&keys, &ValidationPolicy::default()).is_ok());
+    let sizes = ValidationPolicy {
+        max_rows: Some(1),
+        ..ValidationPolicy::default()
+    };
+    assert!(validate(&keys, &sizes).is_ok());
+    let isolated = ValidationPolicy {
+        forbid_isolated_regions: true,
+        ..ValidationPolicy::default()
+    };
+    assert!(validate(&keys, &isolated).is_ok());
+
+    let err = validate(&keys, &policy()).unwrap_err();
+    assert!(err.starts_with("maze is too large to search for keys"), "{}", err);
+}